@@ -26,7 +26,7 @@ use test::Bencher;
 mod common;
 use common::*;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use parquet::{
   basic::*,
@@ -41,9 +41,9 @@ macro_rules! plain {
    $gen_data_fn:expr) => {
     #[bench]
     fn $fname(bench: &mut Bencher) {
-      let mem_tracker = Rc::new(MemTracker::new());
+      let mem_tracker = Arc::new(MemTracker::new());
       let mut encoder =
-        PlainEncoder::<$ty>::new(Rc::new(col_desc(0, $pty)), mem_tracker, vec![]);
+        PlainEncoder::<$ty>::new(Arc::new(col_desc(0, $pty)), mem_tracker, vec![]);
 
       let (_, values) = $gen_data_fn($num_values);
       encoder.put(&values[..]).expect("put() should be OK");
@@ -60,8 +60,8 @@ macro_rules! dict {
    $gen_data_fn:expr) => {
     #[bench]
     fn $fname(bench: &mut Bencher) {
-      let mem_tracker = Rc::new(MemTracker::new());
-      let mut encoder = DictEncoder::<$ty>::new(Rc::new(col_desc(0, $pty)), mem_tracker);
+      let mem_tracker = Arc::new(MemTracker::new());
+      let mut encoder = DictEncoder::<$ty>::new(Arc::new(col_desc(0, $pty)), mem_tracker);
 
       let (_, values) = $gen_data_fn($num_values);
       encoder.put(&values[..]).expect("put() should be OK");
@@ -26,7 +26,7 @@ use test::Bencher;
 mod common;
 use common::*;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use parquet::{basic::*, data_type::*, encoding::*, memory::MemTracker};
 
@@ -34,9 +34,9 @@ macro_rules! plain {
   ($fname:ident, $batch_size:expr, $ty:ident, $pty:expr, $gen_data_fn:expr) => {
     #[bench]
     fn $fname(bench: &mut Bencher) {
-      let mem_tracker = Rc::new(MemTracker::new());
+      let mem_tracker = Arc::new(MemTracker::new());
       let encoder =
-        PlainEncoder::<$ty>::new(Rc::new(col_desc(0, $pty)), mem_tracker, vec![]);
+        PlainEncoder::<$ty>::new(Arc::new(col_desc(0, $pty)), mem_tracker, vec![]);
       let (bytes, values) = $gen_data_fn($batch_size);
       bench_encoding(bench, bytes, values, Box::new(encoder));
     }
@@ -47,8 +47,8 @@ macro_rules! dict {
   ($fname:ident, $batch_size:expr, $ty:ident, $pty:expr, $gen_data_fn:expr) => {
     #[bench]
     fn $fname(bench: &mut Bencher) {
-      let mem_tracker = Rc::new(MemTracker::new());
-      let encoder = DictEncoder::<$ty>::new(Rc::new(col_desc(0, $pty)), mem_tracker);
+      let mem_tracker = Arc::new(MemTracker::new());
+      let encoder = DictEncoder::<$ty>::new(Arc::new(col_desc(0, $pty)), mem_tracker);
       let (bytes, values) = $gen_data_fn($batch_size);
       bench_encoding(bench, bytes, values, Box::new(encoder));
     }
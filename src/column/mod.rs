@@ -36,7 +36,7 @@
 //! repetition levels and read them to verify write/read correctness.
 //!
 //! ```rust
-//! use std::{fs, path::Path, rc::Rc};
+//! use std::{fs, path::Path, rc::Rc, sync::Arc};
 //!
 //! use parquet::{
 //!   column::{reader::ColumnReader, writer::ColumnWriter},
@@ -61,7 +61,7 @@
 //!     }
 //!   }
 //! ";
-//! let schema = Rc::new(parse_message_type(message_type).unwrap());
+//! let schema = Arc::new(parse_message_type(message_type).unwrap());
 //! let props = Rc::new(WriterProperties::builder().build());
 //! let file = fs::File::create(path).unwrap();
 //! let mut writer = SerializedFileWriter::new(file, schema, props).unwrap();
@@ -20,6 +20,7 @@
 use basic::{Encoding, PageType};
 use errors::Result;
 use file::{metadata::ColumnChunkMetaData, statistics::Statistics};
+use parquet_format::PageHeader;
 use util::memory::ByteBufferPtr;
 
 /// Parquet Page definition.
@@ -192,6 +193,18 @@ pub trait PageWriter {
   /// either data page or dictionary page.
   fn write_page(&mut self, page: CompressedPage) -> Result<PageWriteSpec>;
 
+  /// Writes a page header and page body as is, without any encoding or compression.
+  /// Returns `PageWriteSpec` with metrics of the written page, same as `write_page`.
+  ///
+  /// This is used to copy pages between files, normally pages read with
+  /// `SerializedPageReader::next_raw`. Body must be the on-disk (compressed) bytes of
+  /// the page, and its length must match compressed page size in the header.
+  fn write_raw_page(
+    &mut self,
+    header: PageHeader,
+    buf: ByteBufferPtr,
+  ) -> Result<PageWriteSpec>;
+
   /// Writes column chunk metadata into the output stream/sink.
   ///
   /// This method is called once before page writer is closed, normally when writes are
@@ -213,6 +213,7 @@ impl<T: DataType> ColumnReaderImpl<T> {
         if let Some(ref mut levels) = def_levels {
           num_def_levels = self
             .read_def_levels(&mut levels[levels_read..levels_read + iter_batch_size])?;
+          self.check_levels_decoded("definition", num_def_levels, iter_batch_size)?;
           for i in levels_read..levels_read + num_def_levels {
             if levels[i] == self.descr.max_def_level() {
               values_to_read += 1;
@@ -229,14 +230,7 @@ impl<T: DataType> ColumnReaderImpl<T> {
         if let Some(ref mut levels) = rep_levels {
           num_rep_levels = self
             .read_rep_levels(&mut levels[levels_read..levels_read + iter_batch_size])?;
-
-          // If definition levels are defined, check that rep levels == def levels
-          if def_levels.is_some() {
-            assert_eq!(
-              num_def_levels, num_rep_levels,
-              "Number of decoded rep / def levels did not match"
-            );
-          }
+          self.check_levels_decoded("repetition", num_rep_levels, iter_batch_size)?;
         }
       }
 
@@ -252,6 +246,15 @@ impl<T: DataType> ColumnReaderImpl<T> {
       let curr_values_read =
         self.read_values(&mut values[values_read..values_read + values_to_read])?;
 
+      // Definition levels define exact number of non-null values in the page
+      if num_def_levels > 0 && curr_values_read != values_to_read {
+        return Err(general_err!(
+          "Definition levels have {} non-null values, but {} values were decoded",
+          values_to_read,
+          curr_values_read
+        ));
+      }
+
       // Update all "return" counters and internal state.
 
       // This is to account for when def or rep levels are not provided
@@ -264,6 +267,27 @@ impl<T: DataType> ColumnReaderImpl<T> {
     Ok((values_read, levels_read))
   }
 
+  /// Checks that the number of decoded levels matches the number of values left in the
+  /// current data page, otherwise the page is corrupt.
+  #[inline]
+  fn check_levels_decoded(
+    &self,
+    level_type: &str,
+    levels_decoded: usize,
+    levels_expected: usize,
+  ) -> Result<()>
+  {
+    if levels_decoded != levels_expected {
+      return Err(general_err!(
+        "Data page has {} values, but only {} {} levels could be decoded",
+        self.num_buffered_values,
+        self.num_decoded_values as usize + levels_decoded,
+        level_type
+      ));
+    }
+    Ok(())
+  }
+
   /// Reads a new page and set up the decoders for levels, values or dictionary.
   /// Returns false if there's no page left.
   fn read_new_page(&mut self) -> Result<bool> {
@@ -388,6 +412,12 @@ impl<T: DataType> ColumnReaderImpl<T> {
     len: usize,
   ) -> Result<()>
   {
+    // Page has empty value section when all values are null, there is nothing to decode
+    if offset >= buffer_ptr.len() {
+      self.current_encoding = None;
+      return Ok(());
+    }
+
     if encoding == Encoding::PLAIN_DICTIONARY {
       encoding = Encoding::RLE_DICTIONARY;
     }
@@ -449,9 +479,16 @@ impl<T: DataType> ColumnReaderImpl<T> {
 
   #[inline]
   fn read_values(&mut self, buffer: &mut [T::T]) -> Result<usize> {
-    let encoding = self
-      .current_encoding
-      .expect("current_encoding should be set");
+    let encoding = match self.current_encoding {
+      Some(encoding) => encoding,
+      None if buffer.is_empty() => return Ok(0),
+      None => {
+        return Err(general_err!(
+          "Data page has no values, but {} values are expected",
+          buffer.len()
+        ))
+      },
+    };
     let current_decoder = self
       .decoders
       .get_mut(&encoding)
@@ -492,7 +529,7 @@ impl<T: DataType> ColumnReaderImpl<T> {
 mod tests {
   use super::*;
   use rand::distributions::range::SampleRange;
-  use std::{collections::VecDeque, rc::Rc, vec::IntoIter};
+  use std::{collections::VecDeque, sync::Arc, vec::IntoIter};
 
   use basic::Type as PhysicalType;
   use column::page::Page;
@@ -555,8 +592,8 @@ mod tests {
      $min:expr, $max:expr) => {
       #[test]
       fn $test_func() {
-        let desc = Rc::new(ColumnDescriptor::new(
-          Rc::new($pty()),
+        let desc = Arc::new(ColumnDescriptor::new(
+          Arc::new($pty()),
           None,
           $def_level,
           $rep_level,
@@ -951,8 +988,8 @@ mod tests {
     // Note: values are chosen to reproduce the issue.
     //
     let primitive_type = get_test_int32_type();
-    let desc = Rc::new(ColumnDescriptor::new(
-      Rc::new(primitive_type),
+    let desc = Arc::new(ColumnDescriptor::new(
+      Arc::new(primitive_type),
       None,
       1,
       1,
@@ -982,6 +1019,145 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_read_batch_required_column_without_levels() {
+    let desc = Arc::new(ColumnDescriptor::new(
+      Arc::new(get_test_int32_type()),
+      None,
+      0,
+      0,
+      ColumnPath::new(Vec::new()),
+    ));
+
+    // Pages contain only PLAIN encoded values, no level data is written
+    let mut pages = Vec::new();
+    for &datapage_v2 in &[false, true] {
+      let mut pb = DataPageBuilderImpl::new(desc.clone(), 4, datapage_v2);
+      pb.add_values::<Int32Type>(Encoding::PLAIN, &[1, 2, 3, 4]);
+      pages.push(pb.consume());
+    }
+
+    let page_reader = TestPageReader::new(pages);
+    let mut reader = ColumnReaderImpl::<Int32Type>::new(desc, Box::new(page_reader));
+
+    let mut values = vec![0; 16];
+    let mut def_levels = vec![-1; 16];
+    let mut rep_levels = vec![-1; 16];
+    let (values_read, levels_read) = reader
+      .read_batch(16, Some(&mut def_levels), Some(&mut rep_levels), &mut values)
+      .expect("read_batch() should be OK");
+
+    assert_eq!(values_read, 8);
+    assert_eq!(levels_read, 0);
+    assert_eq!(&values[..8], &[1, 2, 3, 4, 1, 2, 3, 4]);
+    // Levels are not touched for required non-repeated column
+    assert_eq!(def_levels, vec![-1; 16]);
+    assert_eq!(rep_levels, vec![-1; 16]);
+
+    // Column is exhausted
+    assert_eq!(reader.read_batch(16, None, None, &mut values).unwrap(), (0, 0));
+  }
+
+  #[test]
+  fn test_read_batch_num_values_mismatch() {
+    let desc = Arc::new(ColumnDescriptor::new(
+      Arc::new(get_test_int32_optional_type()),
+      None,
+      1,
+      0,
+      ColumnPath::new(Vec::new()),
+    ));
+
+    for &datapage_v2 in &[false, true] {
+      // Page header declares more values than definition levels are encoded
+      let mut pb = DataPageBuilderImpl::new(desc.clone(), 4, datapage_v2);
+      pb.add_def_levels(1, &[1, 0, 1, 1]);
+      pb.add_values::<Int32Type>(Encoding::PLAIN, &[1, 2, 3]);
+      let page = match pb.consume() {
+        Page::DataPage { buf, encoding, def_level_encoding, rep_level_encoding, .. } => {
+          Page::DataPage {
+            buf,
+            num_values: 64,
+            encoding,
+            def_level_encoding,
+            rep_level_encoding,
+            statistics: None,
+          }
+        },
+        Page::DataPageV2 {
+          buf,
+          encoding,
+          num_nulls,
+          def_levels_byte_len,
+          rep_levels_byte_len,
+          is_compressed,
+          ..
+        } => Page::DataPageV2 {
+          buf,
+          num_values: 64,
+          encoding,
+          num_nulls,
+          num_rows: 64,
+          def_levels_byte_len,
+          rep_levels_byte_len,
+          is_compressed,
+          statistics: None,
+        },
+        _ => unreachable!(),
+      };
+
+      let page_reader = TestPageReader::new(vec![page]);
+      let mut reader =
+        ColumnReaderImpl::<Int32Type>::new(desc.clone(), Box::new(page_reader));
+      let mut values = vec![0; 64];
+      let mut def_levels = vec![0; 64];
+      let res = reader.read_batch(64, Some(&mut def_levels), None, &mut values);
+      assert!(res.is_err());
+      assert!(
+        format!("{}", res.unwrap_err())
+          .starts_with("Parquet error: Data page has 64 values, but only")
+      );
+    }
+  }
+
+  #[test]
+  fn test_read_batch_non_null_values_mismatch() {
+    let desc = Arc::new(ColumnDescriptor::new(
+      Arc::new(get_test_int32_optional_type()),
+      None,
+      1,
+      0,
+      ColumnPath::new(Vec::new()),
+    ));
+
+    // Dictionary has enough entries, but data page has fewer indices than non-null
+    // definition levels
+    let mut dict_encoder =
+      DictEncoder::<Int32Type>::new(desc.clone(), Arc::new(MemTracker::new()));
+    dict_encoder.put(&[10, 20]).unwrap();
+    let indices = dict_encoder.write_indices().unwrap();
+    let dict_page = Page::DictionaryPage {
+      buf: dict_encoder.write_dict().unwrap(),
+      num_values: dict_encoder.num_entries() as u32,
+      encoding: Encoding::RLE_DICTIONARY,
+      is_sorted: false,
+    };
+
+    let mut pb = DataPageBuilderImpl::new(desc.clone(), 16, false);
+    pb.add_def_levels(1, &[1; 16]);
+    pb.add_indices(indices);
+
+    let page_reader = TestPageReader::new(vec![dict_page, pb.consume()]);
+    let mut reader = ColumnReaderImpl::<Int32Type>::new(desc, Box::new(page_reader));
+    let mut values = vec![0; 16];
+    let mut def_levels = vec![0; 16];
+    let res = reader.read_batch(16, Some(&mut def_levels), None, &mut values);
+    assert_eq!(
+      res.unwrap_err(),
+      general_err!("Definition levels have 16 non-null values, but 8 values were decoded")
+    );
+  }
+
   // ----------------------------------------------------------------------
   // Helper methods to make pages and test
   //
@@ -1025,6 +1201,15 @@ mod tests {
   // reader -> typed column reader, buffer values in `read_batch` method and compare
   // output with generated data.
 
+  // Returns dummy Parquet `Type` for optional INT32 field.
+  fn get_test_int32_optional_type() -> SchemaType {
+    SchemaType::primitive_type_builder("a", PhysicalType::INT32)
+      .with_repetition(Repetition::OPTIONAL)
+      .with_logical_type(LogicalType::INT_32)
+      .build()
+      .expect("build() should be OK")
+  }
+
   // Returns dummy Parquet `Type` for primitive field, because most of our tests use
   // INT32 physical type.
   fn get_test_int32_type() -> SchemaType {
@@ -1070,8 +1255,8 @@ mod tests {
       0
     };
 
-    let desc = Rc::new(ColumnDescriptor::new(
-      Rc::new(primitive_type),
+    let desc = Arc::new(ColumnDescriptor::new(
+      Arc::new(primitive_type),
       None,
       max_def_level,
       max_rep_level,
@@ -1402,7 +1587,7 @@ mod tests {
       DataPageBuilderImpl {
         desc,
         encoding: None,
-        mem_tracker: Rc::new(MemTracker::new()),
+        mem_tracker: Arc::new(MemTracker::new()),
         num_values,
         buffer: vec![],
         rep_levels_byte_len: 0,
@@ -1514,7 +1699,7 @@ mod tests {
     let max_def_level = desc.max_def_level();
     let max_rep_level = desc.max_rep_level();
 
-    let mem_tracker = Rc::new(MemTracker::new());
+    let mem_tracker = Arc::new(MemTracker::new());
     let mut dict_encoder = DictEncoder::<T>::new(desc.clone(), mem_tracker);
 
     for i in 0..num_pages {
@@ -17,7 +17,7 @@
 
 //! Contains column writer API.
 
-use std::{cmp, collections::VecDeque, mem, rc::Rc};
+use std::{cmp, collections::VecDeque, mem, sync::Arc};
 
 use basic::{Compression, Encoding, PageType, Type};
 use column::page::{CompressedPage, Page, PageWriteSpec, PageWriter};
@@ -146,7 +146,7 @@ impl<T: DataType> ColumnWriterImpl<T> {
     // Optionally set dictionary encoder.
     let dict_encoder =
       if props.dictionary_enabled(descr.path()) && Self::has_dictionary_support(&props) {
-        Some(DictEncoder::new(descr.clone(), Rc::new(MemTracker::new())))
+        Some(DictEncoder::new(descr.clone(), Arc::new(MemTracker::new())))
       } else {
         None
       };
@@ -160,7 +160,7 @@ impl<T: DataType> ColumnWriterImpl<T> {
       props
         .encoding(descr.path())
         .unwrap_or(Self::fallback_encoding(&props)),
-      Rc::new(MemTracker::new()),
+      Arc::new(MemTracker::new()),
     )
     .unwrap();
 
@@ -226,14 +226,14 @@ impl<T: DataType> ColumnWriterImpl<T> {
       min_len = cmp::min(min_len, levels.len());
     }
 
-    // Find out number of batches to process.
-    let write_batch_size = self.props.write_batch_size();
-    let num_batches = min_len / write_batch_size;
-
     let mut values_offset = 0;
     let mut levels_offset = 0;
 
-    for _ in 0..num_batches {
+    loop {
+      let write_batch_size = self.next_mini_batch_size();
+      if min_len - levels_offset < write_batch_size {
+        break;
+      }
       values_offset += self.write_mini_batch(
         &values[values_offset..values_offset + write_batch_size],
         def_levels.map(|lv| &lv[levels_offset..levels_offset + write_batch_size]),
@@ -274,6 +274,23 @@ impl<T: DataType> ColumnWriterImpl<T> {
     Ok((self.total_bytes_written, self.total_rows_written, metadata))
   }
 
+  /// Returns size of the next mini batch to write.
+  ///
+  /// For non-repeated columns each value is a row, so mini batch is also bounded by the
+  /// number of rows left before data page row count limit is reached.
+  #[inline]
+  fn next_mini_batch_size(&self) -> usize {
+    let write_batch_size = self.props.write_batch_size();
+    if self.descr.max_rep_level() > 0 {
+      return write_batch_size;
+    }
+    let rows_left = self
+      .props
+      .data_page_row_count_limit()
+      .saturating_sub(self.num_buffered_rows as usize);
+    cmp::max(cmp::min(write_batch_size, rows_left), 1)
+  }
+
   /// Writes mini batch of values, definition and repetition levels.
   /// This allows fine-grained processing of values and maintaining a reasonable
   /// page size.
@@ -404,9 +421,11 @@ impl<T: DataType> ColumnWriterImpl<T> {
   }
 
   /// Returns true if there is enough data for a data page, false otherwise.
+  /// Either data page size limit or row count limit, whichever is reached first.
   #[inline]
   fn should_add_data_page(&self) -> bool {
-    self.encoder.estimated_data_encoded_size() >= self.props.data_pagesize_limit()
+    self.num_buffered_rows as usize >= self.props.data_page_row_count_limit()
+      || self.encoder.estimated_data_encoded_size() >= self.props.data_pagesize_limit()
   }
 
   /// Performs dictionary fallback.
@@ -422,10 +441,16 @@ impl<T: DataType> ColumnWriterImpl<T> {
   /// Adds data page.
   /// Data page is either buffered in case of dictionary encoding or written directly.
   fn add_data_page(&mut self) -> Result<()> {
-    // Extract encoded values
-    let value_bytes = match self.dict_encoder {
-      Some(ref mut encoder) => encoder.write_indices()?,
-      None => self.encoder.flush_buffer()?,
+    // Extract encoded values.
+    // If all values in the page are null, value section is left empty, since there is
+    // nothing to encode.
+    let value_bytes = if self.num_buffered_encoded_values == 0 {
+      ByteBufferPtr::new(vec![])
+    } else {
+      match self.dict_encoder {
+        Some(ref mut encoder) => encoder.write_indices()?,
+        None => self.encoder.flush_buffer()?,
+      }
     };
 
     // Select encoding based on current encoder and writer version (v1 or v2).
@@ -503,14 +528,18 @@ impl<T: DataType> ColumnWriterImpl<T> {
         let uncompressed_size =
           rep_levels_byte_len + def_levels_byte_len + value_bytes.len();
 
-        // Data Page v2 compresses values only.
+        // Data Page v2 compresses values only, empty value section or values smaller
+        // than minimum compression size are not compressed.
+        let is_compressed = self.compressor.is_some()
+          && value_bytes.len() > 0
+          && value_bytes.len() >= self.props.min_compress_size();
         match self.compressor {
-          Some(ref mut cmpr) => {
+          Some(ref mut cmpr) if is_compressed => {
             let mut compressed_buf = Vec::with_capacity(value_bytes.data().len());
             cmpr.compress(value_bytes.data(), &mut compressed_buf)?;
             buffer.extend_from_slice(&compressed_buf[..]);
           },
-          None => {
+          _ => {
             buffer.extend_from_slice(value_bytes.data());
           },
         }
@@ -523,7 +552,7 @@ impl<T: DataType> ColumnWriterImpl<T> {
           num_rows: self.num_buffered_rows,
           def_levels_byte_len: def_levels_byte_len as u32,
           rep_levels_byte_len: rep_levels_byte_len as u32,
-          is_compressed: self.compressor.is_some(),
+          is_compressed,
           // TODO: process statistics
           statistics: None,
         };
@@ -804,8 +833,9 @@ mod tests {
     properties::WriterProperties, reader::SerializedPageReader,
     writer::SerializedPageWriter,
   };
+  use parquet_format::PageHeader;
   use schema::types::{ColumnDescriptor, ColumnPath, Type as SchemaType};
-  use std::error::Error;
+  use std::{cell::RefCell, error::Error, rc::Rc};
   use util::{
     io::{FileSink, FileSource},
     test_common::{get_temp_file, random_numbers_range},
@@ -1282,6 +1312,53 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_column_writer_data_page_row_count_limit() {
+    // Data is compact enough to fit into a single page with default size limit
+    let props = WriterProperties::builder()
+      .set_dictionary_enabled(false)
+      .set_data_page_row_count_limit(10)
+      .build();
+    let pages = write_and_collect_pages(props, &[7, 88]);
+    assert_eq!(pages, vec![10, 10, 10, 10, 10, 10, 10, 10, 10, 5]);
+
+    // Page size limit is reached first: 16 bytes hold 4 plain encoded INT32 values
+    let props = WriterProperties::builder()
+      .set_dictionary_enabled(false)
+      .set_write_batch_size(2)
+      .set_data_pagesize_limit(16)
+      .set_data_page_row_count_limit(10)
+      .build();
+    let pages = write_and_collect_pages(props, &[25]);
+    assert_eq!(pages, vec![4, 4, 4, 4, 4, 4, 1]);
+
+    // Row count limit is reached first
+    let props = WriterProperties::builder()
+      .set_dictionary_enabled(false)
+      .set_write_batch_size(2)
+      .set_data_pagesize_limit(16)
+      .set_data_page_row_count_limit(3)
+      .build();
+    let pages = write_and_collect_pages(props, &[25]);
+    assert_eq!(pages, vec![3, 3, 3, 3, 3, 3, 3, 3, 1]);
+  }
+
+  #[test]
+  fn test_column_writer_data_page_row_count_limit_roundtrip() {
+    let props = WriterProperties::builder()
+      .set_data_page_row_count_limit(7)
+      .build();
+    column_roundtrip_random::<Int32Type>(
+      "test_col_writer_rnd_row_count_limit",
+      props,
+      1024,
+      ::std::i32::MIN,
+      ::std::i32::MAX,
+      10,
+      10,
+    );
+  }
+
   #[test]
   fn test_column_writer_dictionary_disabled_v1() {
     let props = WriterProperties::builder()
@@ -1350,6 +1427,196 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_column_writer_min_compress_size() {
+    for &min_compress_size in &[0, 64] {
+      let props = WriterProperties::builder()
+        .set_writer_version(WriterVersion::PARQUET_2_0)
+        .set_compression(Compression::SNAPPY)
+        .set_dictionary_enabled(false)
+        .set_min_compress_size(min_compress_size)
+        .build();
+
+      let file = get_temp_file("test_col_writer_min_compress_size", &[]);
+      let page_writer = Box::new(SerializedPageWriter::new(FileSink::new(&file)));
+      let mut writer =
+        get_test_column_writer::<Int32Type>(page_writer, 0, 0, Rc::new(props));
+      writer.write_batch(&[1, 2], None, None).unwrap();
+      let (bytes_written, _, column_metadata) = writer.close().unwrap();
+
+      let get_page_reader = || {
+        let source = FileSource::new(&file, 0, bytes_written as usize);
+        Box::new(
+          SerializedPageReader::new(
+            source,
+            column_metadata.num_values(),
+            column_metadata.compression(),
+            Type::INT32,
+          )
+          .unwrap(),
+        )
+      };
+
+      match get_page_reader().get_next_page().unwrap() {
+        Some(Page::DataPageV2 { is_compressed, .. }) => {
+          assert_eq!(is_compressed, min_compress_size == 0);
+        },
+        _ => panic!("Expected data page v2"),
+      }
+      if min_compress_size == 0 {
+        // Snappy adds its own header to 8 bytes of values
+        assert!(column_metadata.compressed_size() > column_metadata.uncompressed_size());
+      } else {
+        assert_eq!(
+          column_metadata.compressed_size(),
+          column_metadata.uncompressed_size()
+        );
+      }
+
+      let reader = get_test_column_reader::<Int32Type>(get_page_reader(), 0, 0);
+      let mut actual_values = vec![0; 4];
+      let (values_read, _) = read_fully(reader, 4, None, None, &mut actual_values);
+      assert_eq!(&actual_values[..values_read], &[1, 2]);
+    }
+
+    // Levels alone exceed minimum compression size, but values do not
+    let props = WriterProperties::builder()
+      .set_writer_version(WriterVersion::PARQUET_2_0)
+      .set_compression(Compression::SNAPPY)
+      .set_dictionary_enabled(false)
+      .set_min_compress_size(100)
+      .build();
+    let def_levels: Vec<i16> = (0..1024).map(|i| i % 2).collect();
+    let values = vec![true; 512];
+
+    let file = get_temp_file("test_col_writer_min_compress_size_levels", &[]);
+    let page_writer = Box::new(SerializedPageWriter::new(FileSink::new(&file)));
+    let mut writer =
+      get_test_column_writer::<BoolType>(page_writer, 1, 0, Rc::new(props));
+    writer.write_batch(&values, Some(&def_levels), None).unwrap();
+    let (bytes_written, _, column_metadata) = writer.close().unwrap();
+
+    let get_page_reader = || {
+      let source = FileSource::new(&file, 0, bytes_written as usize);
+      Box::new(
+        SerializedPageReader::new(
+          source,
+          column_metadata.num_values(),
+          column_metadata.compression(),
+          Type::BOOLEAN,
+        )
+        .unwrap(),
+      )
+    };
+
+    match get_page_reader().get_next_page().unwrap() {
+      Some(Page::DataPageV2 {
+        is_compressed,
+        def_levels_byte_len,
+        ..
+      }) => {
+        assert!(def_levels_byte_len >= 100);
+        assert!(!is_compressed);
+      },
+      _ => panic!("Expected data page v2"),
+    }
+    assert_eq!(
+      column_metadata.compressed_size(),
+      column_metadata.uncompressed_size()
+    );
+
+    let reader = get_test_column_reader::<BoolType>(get_page_reader(), 1, 0);
+    let mut actual_def_levels = vec![0; 1024];
+    let mut actual_values = vec![false; 1024];
+    let (values_read, levels_read) = read_fully(
+      reader,
+      1024,
+      Some(&mut actual_def_levels),
+      None,
+      &mut actual_values,
+    );
+    assert_eq!(levels_read, 1024);
+    assert_eq!(actual_def_levels, def_levels);
+    assert_eq!(&actual_values[..values_read], &values[..]);
+  }
+
+  #[test]
+  fn test_column_writer_all_null_values_roundtrip() {
+    let def_levels = vec![0; 10];
+    for &version in &[WriterVersion::PARQUET_1_0, WriterVersion::PARQUET_2_0] {
+      for &codec in &[Compression::UNCOMPRESSED, Compression::SNAPPY] {
+        for &dictionary_enabled in &[false, true] {
+          let props = WriterProperties::builder()
+            .set_writer_version(version)
+            .set_compression(codec)
+            .set_dictionary_enabled(dictionary_enabled)
+            .build();
+
+          let file = get_temp_file("test_col_writer_all_nulls", &[]);
+          let page_writer = Box::new(SerializedPageWriter::new(FileSink::new(&file)));
+          let mut writer =
+            get_test_column_writer::<Int32Type>(page_writer, 1, 0, Rc::new(props));
+          writer.write_batch(&[], Some(&def_levels), None).unwrap();
+          let (bytes_written, _, column_metadata) = writer.close().unwrap();
+
+          let get_page_reader = || {
+            let source = FileSource::new(&file, 0, bytes_written as usize);
+            Box::new(
+              SerializedPageReader::new(
+                source,
+                column_metadata.num_values(),
+                column_metadata.compression(),
+                Type::INT32,
+              )
+              .unwrap(),
+            )
+          };
+
+          // Data page only contains definition levels
+          let mut page_reader = get_page_reader();
+          let mut num_data_pages = 0;
+          while let Some(page) = page_reader.get_next_page().unwrap() {
+            match page {
+              Page::DataPage { buf, num_values, .. } => {
+                assert_eq!(num_values, 10);
+                // 4 bytes of length + RLE run of 10 zeros
+                assert_eq!(buf.len(), 6);
+              },
+              Page::DataPageV2 {
+                buf,
+                num_values,
+                num_nulls,
+                def_levels_byte_len,
+                ..
+              } => {
+                assert_eq!(num_values, 10);
+                assert_eq!(num_nulls, 10);
+                assert_eq!(buf.len(), def_levels_byte_len as usize);
+              },
+              Page::DictionaryPage { .. } => continue,
+            }
+            num_data_pages += 1;
+          }
+          assert_eq!(num_data_pages, 1);
+
+          let reader = get_test_column_reader::<Int32Type>(get_page_reader(), 1, 0);
+          let mut actual_values = vec![0; 20];
+          let mut actual_def_levels = vec![-1; 20];
+          let (values_read, levels_read) = read_fully(
+            reader,
+            20,
+            Some(&mut actual_def_levels),
+            None,
+            &mut actual_values,
+          );
+          assert_eq!(values_read, 0);
+          assert_eq!(levels_read, 10);
+          assert_eq!(&actual_def_levels[..10], &def_levels[..]);
+        }
+      }
+    }
+  }
+
   /// Performs write-read roundtrip with randomly generated values and levels.
   /// `max_size` is maximum number of values or levels (if `max_def_level` > 0) to write
   /// for a column.
@@ -1561,7 +1828,7 @@ mod tests {
     props: WriterPropertiesPtr,
   ) -> ColumnWriterImpl<T>
   {
-    let descr = Rc::new(get_test_column_descr::<T>(max_def_level, max_rep_level));
+    let descr = Arc::new(get_test_column_descr::<T>(max_def_level, max_rep_level));
     let column_writer = get_column_writer(descr, props, page_writer);
     get_typed_column_writer::<T>(column_writer)
   }
@@ -1573,7 +1840,7 @@ mod tests {
     max_rep_level: i16,
   ) -> ColumnReaderImpl<T>
   {
-    let descr = Rc::new(get_test_column_descr::<T>(max_def_level, max_rep_level));
+    let descr = Arc::new(get_test_column_descr::<T>(max_def_level, max_rep_level));
     let column_reader = get_column_reader(descr, page_reader);
     get_typed_column_reader::<T>(column_reader)
   }
@@ -1591,7 +1858,51 @@ mod tests {
       .with_length(1)
       .build()
       .unwrap();
-    ColumnDescriptor::new(Rc::new(tpe), None, max_def_level, max_rep_level, path)
+    ColumnDescriptor::new(Arc::new(tpe), None, max_def_level, max_rep_level, path)
+  }
+
+  /// Writes batches of given lengths into a required INT32 column and returns number of
+  /// values for each data page written.
+  fn write_and_collect_pages(props: WriterProperties, batches: &[usize]) -> Vec<u32> {
+    let pages = Rc::new(RefCell::new(Vec::new()));
+    let page_writer = Box::new(CollectingPageWriter {
+      pages: pages.clone(),
+    });
+    let mut writer =
+      get_test_column_writer::<Int32Type>(page_writer, 0, 0, Rc::new(props));
+    for &len in batches {
+      writer.write_batch(&vec![1; len], None, None).unwrap();
+    }
+    writer.close().unwrap();
+    let res = pages.borrow().clone();
+    res
+  }
+
+  /// Page writer that records number of values for each data page.
+  struct CollectingPageWriter {
+    pages: Rc<RefCell<Vec<u32>>>,
+  }
+
+  impl PageWriter for CollectingPageWriter {
+    fn write_page(&mut self, page: CompressedPage) -> Result<PageWriteSpec> {
+      if page.page_type() != PageType::DICTIONARY_PAGE {
+        self.pages.borrow_mut().push(page.num_values());
+      }
+      TestPageWriter {}.write_page(page)
+    }
+
+    fn write_raw_page(
+      &mut self,
+      header: PageHeader,
+      buf: ByteBufferPtr,
+    ) -> Result<PageWriteSpec>
+    {
+      TestPageWriter {}.write_raw_page(header, buf)
+    }
+
+    fn write_metadata(&mut self, _metadata: &ColumnChunkMetaData) -> Result<()> { Ok(()) }
+
+    fn close(&mut self) -> Result<()> { Ok(()) }
   }
 
   /// Returns page writer that collects pages without serializing them.
@@ -1611,6 +1922,21 @@ mod tests {
       Ok(res)
     }
 
+    fn write_raw_page(
+      &mut self,
+      header: PageHeader,
+      buf: ByteBufferPtr,
+    ) -> Result<PageWriteSpec>
+    {
+      let mut res = PageWriteSpec::new();
+      res.page_type = PageType::from(header.type_);
+      res.uncompressed_size = header.uncompressed_page_size as usize;
+      res.compressed_size = header.compressed_page_size as usize;
+      res.offset = 0;
+      res.bytes_written = buf.len() as u64;
+      Ok(res)
+    }
+
     fn write_metadata(&mut self, _metadata: &ColumnChunkMetaData) -> Result<()> { Ok(()) }
 
     fn close(&mut self) -> Result<()> { Ok(()) }
@@ -18,10 +18,11 @@
 //! Data types that connect Parquet physical types with their Rust-specific
 //! representations.
 
-use std::mem;
+use std::{cmp::Ordering, fmt, mem};
 
 use basic::Type;
-use byteorder::{BigEndian, ByteOrder};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use num_bigint::{BigInt, Sign};
 use util::memory::{ByteBuffer, ByteBufferPtr};
 
 /// Rust representation for logical type INT96, value is backed by an array of `u32`.
@@ -45,6 +46,15 @@ impl Int96 {
   pub fn set_data(&mut self, elem0: u32, elem1: u32, elem2: u32) {
     self.value = Some([elem0, elem1, elem2]);
   }
+
+  /// Returns Julian day of the timestamp, stored in the last 4 bytes.
+  pub fn julian_day(&self) -> u32 { self.data()[2] }
+
+  /// Returns nanoseconds elapsed since midnight, stored in the first 8 bytes.
+  pub fn nanos_of_day(&self) -> u64 {
+    let data = self.data();
+    ((data[1] as u64) << 32) | data[0] as u64
+  }
 }
 
 impl Default for Int96 {
@@ -55,6 +65,15 @@ impl PartialEq for Int96 {
   fn eq(&self, other: &Int96) -> bool { self.data() == other.data() }
 }
 
+/// INT96 values are ordered as timestamps: by Julian day first, then by nanoseconds of
+/// the day. This differs from the order of the raw little-endian bytes.
+impl PartialOrd for Int96 {
+  fn partial_cmp(&self, other: &Int96) -> Option<Ordering> {
+    let key = (self.julian_day(), self.nanos_of_day());
+    key.partial_cmp(&(other.julian_day(), other.nanos_of_day()))
+  }
+}
+
 impl From<Vec<u32>> for Int96 {
   fn from(buf: Vec<u32>) -> Self {
     assert_eq!(buf.len(), 3);
@@ -222,6 +241,40 @@ impl Decimal {
   }
 }
 
+impl fmt::Display for Decimal {
+  /// Formats unscaled value with decimal point inserted according to the scale.
+  /// Decimal point is omitted if scale is 0. If scale is negative or not less than
+  /// precision, which is not allowed by Parquet schema, unscaled value is formatted.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    // Specify as signed bytes to resolve sign as part of conversion.
+    let num = BigInt::from_signed_bytes_be(self.data());
+    if self.scale() <= 0 || self.precision() <= self.scale() {
+      return write!(f, "{}", num);
+    }
+
+    // Offset of the first digit in a string.
+    let negative = if num.sign() == Sign::Minus { 1 } else { 0 };
+    let mut num_str = num.to_string();
+    let mut point = num_str.len() as i32 - self.scale() - negative;
+
+    // Convert to string form without scientific notation.
+    if point <= 0 {
+      // Zeros need to be prepended to the unscaled value.
+      while point < 0 {
+        num_str.insert(negative as usize, '0');
+        point += 1;
+      }
+      num_str.insert_str(negative as usize, "0.");
+    } else {
+      // No zeroes need to be prepended to the unscaled value, simply insert decimal
+      // point.
+      num_str.insert((point + negative) as usize, '.');
+    }
+
+    write!(f, "{}", num_str)
+  }
+}
+
 impl Default for Decimal {
   fn default() -> Self { Self::from_i32(0, 0, 0) }
 }
@@ -234,6 +287,64 @@ impl PartialEq for Decimal {
   }
 }
 
+/// Rust representation for Interval values.
+///
+/// This is not a representation of Parquet physical type, but rather a wrapper for
+/// INTERVAL logical type. Value is stored as FIXED_LEN_BYTE_ARRAY of length 12 that
+/// contains three little-endian unsigned integers: number of months, number of days and
+/// number of milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Interval {
+  months: u32,
+  days: u32,
+  millis: u32,
+}
+
+impl Interval {
+  /// Creates new interval value from number of months, days and milliseconds.
+  pub fn new(months: u32, days: u32, millis: u32) -> Self {
+    Self {
+      months,
+      days,
+      millis,
+    }
+  }
+
+  /// Creates new interval value from 12 bytes of INTERVAL value.
+  pub fn from_bytes(bytes: &[u8]) -> Self {
+    assert_eq!(bytes.len(), 12);
+    Self::new(
+      LittleEndian::read_u32(&bytes[0..4]),
+      LittleEndian::read_u32(&bytes[4..8]),
+      LittleEndian::read_u32(&bytes[8..12]),
+    )
+  }
+
+  /// Returns 12 bytes of INTERVAL value.
+  pub fn to_bytes(&self) -> [u8; 12] {
+    let mut bytes = [0; 12];
+    LittleEndian::write_u32(&mut bytes[0..4], self.months);
+    LittleEndian::write_u32(&mut bytes[4..8], self.days);
+    LittleEndian::write_u32(&mut bytes[8..12], self.millis);
+    bytes
+  }
+
+  /// Returns number of months.
+  pub fn months(&self) -> u32 { self.months }
+
+  /// Returns number of days.
+  pub fn days(&self) -> u32 { self.days }
+
+  /// Returns number of milliseconds.
+  pub fn millis(&self) -> u32 { self.millis }
+}
+
+impl From<Interval> for ByteArray {
+  fn from(interval: Interval) -> ByteArray {
+    ByteArray::from(interval.to_bytes().to_vec())
+  }
+}
+
 /// Converts an instance of data type to a slice of bytes as `u8`.
 pub trait AsBytes {
   /// Returns slice of bytes for this data type.
@@ -396,6 +507,27 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_int96_timestamp_order() {
+    let value = Int96::from(vec![1, 2, 2458000]);
+    assert_eq!(value.julian_day(), 2458000);
+    assert_eq!(value.nanos_of_day(), (2 << 32) + 1);
+
+    // Later day is greater regardless of nanoseconds, even though its first bytes are
+    // smaller
+    let earlier = Int96::from(vec![1, 0, 2457999]);
+    let later = Int96::from(vec![0, 0, 2458000]);
+    assert!(earlier.as_bytes() > later.as_bytes());
+    assert!(earlier < later);
+
+    // Same day is ordered by nanoseconds, high word first
+    let earlier = Int96::from(vec![u32::max_value(), 0, 2458000]);
+    let later = Int96::from(vec![0, 1, 2458000]);
+    assert!(earlier < later);
+    assert!(later > earlier);
+    assert!(later <= later.clone());
+  }
+
   #[test]
   fn test_byte_array_from() {
     assert_eq!(
@@ -427,4 +559,65 @@ mod tests {
 
     assert!(Decimal::from_i64(222, 5, 2) != Decimal::from_i32(222, 5, 2));
   }
+
+  #[test]
+  fn test_interval_from_bytes() {
+    // 14 months, 3 days, 86400000 milliseconds
+    let bytes = [14, 0, 0, 0, 3, 0, 0, 0, 0, 92, 38, 5];
+    let interval = Interval::from_bytes(&bytes);
+    assert_eq!(interval.months(), 14);
+    assert_eq!(interval.days(), 3);
+    assert_eq!(interval.millis(), 86_400_000);
+    assert_eq!(interval, Interval::new(14, 3, 86_400_000));
+    assert_eq!(interval.to_bytes(), bytes);
+    assert_eq!(ByteArray::from(interval).data(), &bytes);
+
+    let interval = Interval::new(::std::u32::MAX, 0, 1);
+    assert_eq!(Interval::from_bytes(&interval.to_bytes()), interval);
+  }
+
+  #[test]
+  fn test_decimal_to_string() {
+    assert_eq!(Decimal::from_i32(1234, 4, 2).to_string(), "12.34");
+    assert_eq!(Decimal::from_i32(-1234, 9, 0).to_string(), "-1234");
+    assert_eq!(Decimal::from_i64(5, 10, 3).to_string(), "0.005");
+    assert_eq!(Decimal::from_i64(-123456789012, 18, 6).to_string(), "-123456.789012");
+    // Big-endian unscaled value backed by FIXED_LEN_BYTE_ARRAY
+    let value = ByteArray::from(vec![0, 0, 0, 0, 0, 4, 147, 224]);
+    assert_eq!(Decimal::from_bytes(value, 17, 5).to_string(), "3.00000");
+    let value = ByteArray::from(vec![255, 255, 255, 156]);
+    assert_eq!(Decimal::from_bytes(value, 5, 1).to_string(), "-10.0");
+    // Invalid scale, unscaled value is formatted
+    assert_eq!(Decimal::from_i32(-1234, 9, -2).to_string(), "-1234");
+    assert_eq!(Decimal::from_i32(1234, 2, 4).to_string(), "1234");
+  }
+
+  #[test]
+  fn test_decimal_display() {
+    // Helper method to compare decimal
+    fn check_decimal(bytes: Vec<u8>, precision: i32, scale: i32, res: &str) {
+      let decimal = Decimal::from_bytes(ByteArray::from(bytes), precision, scale);
+      assert_eq!(decimal.to_string(), res);
+    }
+
+    // This example previously used to fail in some engines
+    check_decimal(
+      vec![0, 0, 0, 0, 0, 0, 0, 0, 13, 224, 182, 179, 167, 100, 0, 0],
+      38,
+      18,
+      "1.000000000000000000",
+    );
+    check_decimal(
+      vec![
+        249, 233, 247, 16, 185, 192, 202, 223, 215, 165, 192, 166, 67, 72,
+      ],
+      36,
+      28,
+      "-12344.0242342304923409234234293432",
+    );
+    check_decimal(vec![0, 0, 0, 0, 0, 4, 147, 224], 17, 5, "3.00000");
+    check_decimal(vec![0, 0, 0, 0, 1, 201, 195, 140], 18, 2, "300000.12");
+    check_decimal(vec![207, 200], 10, 2, "-123.44");
+    check_decimal(vec![207, 200], 10, 8, "-0.00012344");
+  }
 }
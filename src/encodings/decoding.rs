@@ -53,6 +53,57 @@ pub trait Decoder<T: DataType> {
   fn encoding(&self) -> Encoding;
 }
 
+/// Value decoders that [`get_decoder`] can create. This is the only place that maps
+/// encodings to decoders, both `get_decoder` and [`is_decoding_supported`] use it.
+#[derive(Clone, Copy)]
+enum DecoderKind {
+  Plain,
+  Rle,
+  DeltaBinaryPacked,
+  DeltaLengthByteArray,
+  DeltaByteArray,
+}
+
+impl DecoderKind {
+  /// Returns decoder kind for `encoding`, or `None` if there is no value decoder for it.
+  fn from_encoding(encoding: Encoding) -> Option<Self> {
+    match encoding {
+      Encoding::PLAIN => Some(DecoderKind::Plain),
+      Encoding::RLE => Some(DecoderKind::Rle),
+      Encoding::DELTA_BINARY_PACKED => Some(DecoderKind::DeltaBinaryPacked),
+      Encoding::DELTA_LENGTH_BYTE_ARRAY => Some(DecoderKind::DeltaLengthByteArray),
+      Encoding::DELTA_BYTE_ARRAY => Some(DecoderKind::DeltaByteArray),
+      _ => None,
+    }
+  }
+
+  /// Returns `true` if decoder can decode values of `physical_type`.
+  fn supports(&self, physical_type: Type) -> bool {
+    match *self {
+      DecoderKind::Plain => true,
+      DecoderKind::Rle => physical_type == Type::BOOLEAN,
+      DecoderKind::DeltaBinaryPacked => {
+        physical_type == Type::INT32 || physical_type == Type::INT64
+      },
+      DecoderKind::DeltaLengthByteArray => physical_type == Type::BYTE_ARRAY,
+      DecoderKind::DeltaByteArray => {
+        physical_type == Type::BYTE_ARRAY || physical_type == Type::FIXED_LEN_BYTE_ARRAY
+      },
+    }
+  }
+}
+
+/// Returns `true` if values of `physical_type` encoded with `encoding` can be decoded,
+/// either by a decoder from [`get_decoder`] or by dictionary decoding.
+pub fn is_decoding_supported(encoding: Encoding, physical_type: Type) -> bool {
+  match encoding {
+    Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY => true,
+    encoding => DecoderKind::from_encoding(encoding)
+      .map(|kind| kind.supports(physical_type))
+      .unwrap_or(false),
+  }
+}
+
 /// Gets a decoder for the column descriptor `descr` and encoding type `encoding`.
 ///
 /// NOTE: the primitive type in `descr` MUST match the data type `T`, otherwise
@@ -62,18 +113,20 @@ pub fn get_decoder<T: DataType>(
   encoding: Encoding,
 ) -> Result<Box<Decoder<T>>>
 {
-  let decoder: Box<Decoder<T>> = match encoding {
-    Encoding::PLAIN => Box::new(PlainDecoder::new(descr.type_length())),
-    Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY => {
-      return Err(general_err!(
-        "Cannot initialize this encoding through this function"
-      ));
+  if encoding == Encoding::RLE_DICTIONARY || encoding == Encoding::PLAIN_DICTIONARY {
+    return Err(general_err!(
+      "Cannot initialize this encoding through this function"
+    ));
+  }
+  let decoder: Box<Decoder<T>> = match DecoderKind::from_encoding(encoding) {
+    Some(DecoderKind::Plain) => Box::new(PlainDecoder::new(descr.type_length())),
+    Some(DecoderKind::Rle) => Box::new(RleValueDecoder::new()),
+    Some(DecoderKind::DeltaBinaryPacked) => Box::new(DeltaBitPackDecoder::new()),
+    Some(DecoderKind::DeltaLengthByteArray) => {
+      Box::new(DeltaLengthByteArrayDecoder::new())
     },
-    Encoding::RLE => Box::new(RleValueDecoder::new()),
-    Encoding::DELTA_BINARY_PACKED => Box::new(DeltaBitPackDecoder::new()),
-    Encoding::DELTA_LENGTH_BYTE_ARRAY => Box::new(DeltaLengthByteArrayDecoder::new()),
-    Encoding::DELTA_BYTE_ARRAY => Box::new(DeltaByteArrayDecoder::new()),
-    e => return Err(nyi_err!("Encoding {} is not supported", e)),
+    Some(DecoderKind::DeltaByteArray) => Box::new(DeltaByteArrayDecoder::new()),
+    None => return Err(nyi_err!("Encoding {} is not supported", encoding)),
   };
   Ok(decoder)
 }
@@ -210,11 +263,21 @@ impl Decoder<ByteArrayType> for PlainDecoder<ByteArrayType> {
     let data = self.data.as_mut().unwrap();
     let num_values = cmp::min(buffer.len(), self.num_values);
     for i in 0..num_values {
+      // Length prefix and length itself come from the file and cannot be trusted, check
+      // both against remaining bytes for every value.
+      if data.len() < self.start + mem::size_of::<u32>() {
+        return Err(eof_err!("Not enough bytes to decode"));
+      }
       let len: usize =
         read_num_bytes!(u32, 4, data.start_from(self.start).as_ref()) as usize;
       self.start += mem::size_of::<u32>();
-      if data.len() < self.start + len {
-        return Err(eof_err!("Not enough bytes to decode"));
+      let remaining = data.len() - self.start;
+      if len > remaining {
+        return Err(eof_err!(
+          "byte array length {} exceeds remaining {}",
+          len,
+          remaining
+        ));
       }
       buffer[i].set_data(data.range(self.start, len));
       self.start += len;
@@ -838,7 +901,7 @@ impl Decoder<FixedLenByteArrayType> for DeltaByteArrayDecoder<FixedLenByteArrayT
 mod tests {
   use super::{super::encoding::*, *};
   use schema::types::{ColumnDescPtr, ColumnDescriptor, ColumnPath, Type as SchemaType};
-  use std::{mem, rc::Rc};
+  use std::{mem, sync::Arc};
   use util::{bit_util::set_array_bit, memory::MemTracker, test_common::RandGen};
 
   #[test]
@@ -871,6 +934,27 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_is_decoding_supported() {
+    assert!(is_decoding_supported(Encoding::PLAIN, Type::INT96));
+    assert!(is_decoding_supported(Encoding::PLAIN_DICTIONARY, Type::BYTE_ARRAY));
+    assert!(is_decoding_supported(Encoding::RLE_DICTIONARY, Type::DOUBLE));
+    assert!(is_decoding_supported(Encoding::RLE, Type::BOOLEAN));
+    assert!(!is_decoding_supported(Encoding::RLE, Type::INT32));
+    assert!(is_decoding_supported(Encoding::DELTA_BINARY_PACKED, Type::INT64));
+    assert!(!is_decoding_supported(Encoding::DELTA_BINARY_PACKED, Type::FLOAT));
+    assert!(is_decoding_supported(Encoding::DELTA_LENGTH_BYTE_ARRAY, Type::BYTE_ARRAY));
+    assert!(!is_decoding_supported(
+      Encoding::DELTA_LENGTH_BYTE_ARRAY,
+      Type::FIXED_LEN_BYTE_ARRAY
+    ));
+    assert!(is_decoding_supported(
+      Encoding::DELTA_BYTE_ARRAY,
+      Type::FIXED_LEN_BYTE_ARRAY
+    ));
+    assert!(!is_decoding_supported(Encoding::BIT_PACKED, Type::INT32));
+  }
+
   #[test]
   fn test_plain_decode_int32() {
     let data = vec![42, 18, 52];
@@ -977,6 +1061,28 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_plain_decode_byte_array_length_overrun() {
+    // Second value declares 100 bytes, but only 3 bytes are left
+    let data_bytes = vec![2, 0, 0, 0, b'h', b'i', 100, 0, 0, 0, b'a', b'b', b'c'];
+    let mut decoder: PlainDecoder<ByteArrayType> = PlainDecoder::new(-1);
+    decoder.set_data(ByteBufferPtr::new(data_bytes), 2).unwrap();
+    let mut buffer = vec![ByteArray::new(); 2];
+    assert_eq!(
+      decoder.get(&mut buffer[..]).unwrap_err(),
+      eof_err!("byte array length 100 exceeds remaining 3")
+    );
+
+    // Length prefix of the last value is truncated
+    let data_bytes = vec![2, 0, 0, 0, b'h', b'i', 1, 0];
+    let mut decoder: PlainDecoder<ByteArrayType> = PlainDecoder::new(-1);
+    decoder.set_data(ByteBufferPtr::new(data_bytes), 2).unwrap();
+    assert_eq!(
+      decoder.get(&mut buffer[..]).unwrap_err(),
+      eof_err!("Not enough bytes to decode")
+    );
+  }
+
   #[test]
   fn test_plain_decode_fixed_len_byte_array() {
     let mut data = vec![ByteArray::default(); 3];
@@ -994,6 +1100,30 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_plain_decode_interval() {
+    let data = vec![
+      ByteArray::from(Interval::new(1, 2, 3)),
+      ByteArray::from(Interval::new(12, 0, 1000)),
+    ];
+    let data_bytes = FixedLenByteArrayType::to_byte_array(&data[..]);
+    assert_eq!(
+      &data_bytes[..12],
+      &[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0],
+      "Months, days and millis are stored as little-endian u32 values"
+    );
+
+    let mut buffer = vec![ByteArray::default(); 2];
+    test_plain_decode::<FixedLenByteArrayType>(
+      ByteBufferPtr::new(data_bytes),
+      2,
+      12,
+      &mut buffer[..],
+      &data[..],
+    );
+    assert_eq!(Interval::from_bytes(buffer[1].data()), Interval::new(12, 0, 1000));
+  }
+
   #[test]
   #[should_panic(expected = "RleValueEncoder only supports BoolType")]
   fn test_rle_value_encode_int32_not_supported() {
@@ -1241,7 +1371,7 @@ mod tests {
 
     // Encode data
     let mut encoder =
-      get_encoder::<T>(col_descr.clone(), encoding, Rc::new(MemTracker::new()))
+      get_encoder::<T>(col_descr.clone(), encoding, Arc::new(MemTracker::new()))
         .expect("get encoder");
 
     for v in &data[..] {
@@ -1294,8 +1424,8 @@ mod tests {
       .with_length(type_len)
       .build()
       .unwrap();
-    Rc::new(ColumnDescriptor::new(
-      Rc::new(ty),
+    Arc::new(ColumnDescriptor::new(
+      Arc::new(ty),
       None,
       0,
       0,
@@ -955,7 +955,7 @@ impl Encoder<FixedLenByteArrayType> for DeltaByteArrayEncoder<FixedLenByteArrayT
 mod tests {
   use super::{super::decoding::*, *};
   use schema::types::{ColumnDescPtr, ColumnDescriptor, ColumnPath, Type as SchemaType};
-  use std::rc::Rc;
+  use std::sync::Arc;
   use util::{memory::MemTracker, test_common::RandGen};
 
   const TEST_SET_SIZE: usize = 1024;
@@ -1283,7 +1283,7 @@ mod tests {
   )
   {
     let descr = create_test_col_desc_ptr(-1, T::get_physical_type());
-    let mem_tracker = Rc::new(MemTracker::new());
+    let mem_tracker = Arc::new(MemTracker::new());
     let encoder = get_encoder::<T>(descr, encoding, mem_tracker);
     match err {
       Some(parquet_error) => {
@@ -1303,8 +1303,8 @@ mod tests {
       .with_length(type_len)
       .build()
       .unwrap();
-    Rc::new(ColumnDescriptor::new(
-      Rc::new(ty),
+    Arc::new(ColumnDescriptor::new(
+      Arc::new(ty),
       None,
       0,
       0,
@@ -1314,7 +1314,7 @@ mod tests {
 
   fn create_test_encoder<T: DataType>(type_len: i32, enc: Encoding) -> Box<Encoder<T>> {
     let desc = create_test_col_desc_ptr(type_len, T::get_physical_type());
-    let mem_tracker = Rc::new(MemTracker::new());
+    let mem_tracker = Arc::new(MemTracker::new());
     get_encoder(desc, enc, mem_tracker).unwrap()
   }
 
@@ -1325,7 +1325,7 @@ mod tests {
 
   fn create_test_dict_encoder<T: DataType>(type_len: i32) -> DictEncoder<T> {
     let desc = create_test_col_desc_ptr(type_len, T::get_physical_type());
-    let mem_tracker = Rc::new(MemTracker::new());
+    let mem_tracker = Arc::new(MemTracker::new());
     DictEncoder::<T>::new(desc, mem_tracker)
   }
 
@@ -15,14 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::{cmp, mem};
+use std::{cmp, convert::TryFrom, mem};
 
 use super::rle::{RleDecoder, RleEncoder};
 use basic::Encoding;
 use data_type::AsBytes;
 use errors::{ParquetError, Result};
 use util::{
-  bit_util::{ceil, log2, BitReader, BitWriter},
+  bit_util::{ceil, checked_ceil, log2, BitReader, BitWriter},
   memory::ByteBufferPtr,
 };
 
@@ -68,11 +68,10 @@ impl LevelEncoder {
   pub fn v1(encoding: Encoding, max_level: i16, byte_buffer: Vec<u8>) -> Self {
     let bit_width = log2(max_level as u64 + 1) as u8;
     match encoding {
-      Encoding::RLE => LevelEncoder::RLE(RleEncoder::new_from_buf(
-        bit_width,
-        byte_buffer,
-        mem::size_of::<i32>(),
-      )),
+      Encoding::RLE => LevelEncoder::RLE(
+        RleEncoder::new_from_buf(bit_width, byte_buffer, mem::size_of::<i32>())
+          .with_max_value(max_level as u64),
+      ),
       Encoding::BIT_PACKED => {
         // Here we set full byte buffer without adjusting for num_buffered_values,
         // because byte buffer will already be allocated with size from
@@ -87,7 +86,10 @@ impl LevelEncoder {
   /// repetition and definition levels.
   pub fn v2(max_level: i16, byte_buffer: Vec<u8>) -> Self {
     let bit_width = log2(max_level as u64 + 1) as u8;
-    LevelEncoder::RLE_V2(RleEncoder::new_from_buf(bit_width, byte_buffer, 0))
+    LevelEncoder::RLE_V2(
+      RleEncoder::new_from_buf(bit_width, byte_buffer, 0)
+        .with_max_value(max_level as u64),
+    )
   }
 
   /// Put/encode levels vector into this level encoder.
@@ -200,8 +202,13 @@ impl LevelDecoder {
       LevelDecoder::BIT_PACKED(ref mut num_values, bit_width, ref mut decoder) => {
         *num_values = Some(num_buffered_values);
         // Set appropriate number of bytes: if max size is larger than buffer - set full
-        // buffer
-        let num_bytes = ceil((num_buffered_values * bit_width as usize) as i64, 8);
+        // buffer. Number of values comes from page header, so size computation can
+        // overflow for corrupt files, in which case full buffer is used as well.
+        let num_bytes = num_buffered_values
+          .checked_mul(bit_width as usize)
+          .and_then(|num_bits| i64::try_from(num_bits).ok())
+          .and_then(|num_bits| checked_ceil(num_bits, 8))
+          .unwrap_or(data.len() as i64);
         let data_size = cmp::min(num_bytes as usize, data.len());
         decoder.reset(data.range(data.start(), data_size));
         data_size
@@ -483,6 +490,24 @@ mod tests {
     test_internal_roundtrip_overflow(Encoding::RLE, &levels, max_level, true);
   }
 
+  #[test]
+  fn test_rle_level_encoder_above_max_level() {
+    let max_level = 2;
+    let size = max_buffer_size(Encoding::RLE, max_level, 4);
+    for &v2 in &[false, true] {
+      let mut encoder = if v2 {
+        LevelEncoder::v2(max_level, vec![0; size])
+      } else {
+        LevelEncoder::v1(Encoding::RLE, max_level, vec![0; size])
+      };
+      assert_eq!(encoder.put(&[0, 1, 2]).unwrap(), 3);
+      assert_eq!(
+        encoder.put(&[3]).unwrap_err(),
+        general_err!("Value 3 exceeds maximum value 2")
+      );
+    }
+  }
+
   #[test]
   fn test_rle_decoder_set_data_range() {
     // Buffer containing both repetition and definition levels
@@ -527,6 +552,11 @@ mod tests {
     assert_eq!(decoder.set_data(1024, buffer.all()), buffer.len());
     // This should set smallest num bytes
     assert_eq!(decoder.set_data(3, buffer.all()), 1);
+    // Size overflow should reset to entire buffer
+    assert_eq!(
+      decoder.set_data(usize::max_value(), buffer.all()),
+      buffer.len()
+    );
   }
 
   #[test]
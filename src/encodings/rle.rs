@@ -85,6 +85,9 @@ pub struct RleEncoder {
 
   // The position of the indicator byte in the `bit_writer`.
   indicator_byte_pos: i64,
+
+  // Maximum value accepted by `put()`, if set.
+  max_value: Option<u64>,
 }
 
 impl RleEncoder {
@@ -115,9 +118,32 @@ impl RleEncoder {
       repeat_count: 0,
       bit_packed_count: 0,
       indicator_byte_pos: -1,
+      max_value: None,
     }
   }
 
+  /// Creates encoder for definition or repetition levels up to `max_level`.
+  /// Bit width is computed from `max_level`, and `put()` returns error for values
+  /// greater than `max_level`.
+  pub fn for_levels(max_level: i16, buffer_len: usize) -> Self {
+    let bit_width = bit_util::num_required_bits(max_level as u64) as u8;
+    let buffer_len = cmp::max(buffer_len, RleEncoder::min_buffer_size(bit_width));
+    RleEncoder::new(bit_width, buffer_len).with_max_value(max_level as u64)
+  }
+
+  /// Sets maximum value accepted by `put()`. Larger values would not fit into
+  /// `bit_width` bits and corrupt the encoded data, so they are rejected with error.
+  pub fn with_max_value(mut self, max_value: u64) -> Self {
+    assert!(
+      bit_util::num_required_bits(max_value) <= self.bit_width as usize,
+      "max value {} does not fit into bit width {}",
+      max_value,
+      self.bit_width
+    );
+    self.max_value = Some(max_value);
+    self
+  }
+
   /// Returns the minimum buffer size needed to use the encoder for `bit_width`.
   /// This is the maximum length of a single run for `bit_width`.
   pub fn min_buffer_size(bit_width: u8) -> usize {
@@ -156,6 +182,15 @@ impl RleEncoder {
       // The value cannot fit in the current buffer.
       return Ok(false);
     }
+    if let Some(max_value) = self.max_value {
+      if value > max_value {
+        return Err(general_err!(
+          "Value {} exceeds maximum value {}",
+          value,
+          max_value
+        ));
+      }
+    }
     if self.current_value == value {
       self.repeat_count += 1;
       if self.repeat_count > 8 {
@@ -265,11 +300,13 @@ impl RleEncoder {
       self.indicator_byte_pos = self.bit_writer.skip(1)? as i64;
     }
 
-    // Write all buffered values as bit-packed literals
-    for i in 0..self.num_buffered_values {
-      let _ = self
-        .bit_writer
-        .put_value(self.buffered_values[i], self.bit_width as usize);
+    // Write all buffered values as bit-packed literals. Values are always buffered in
+    // groups of 8, which keeps bit-packed runs byte aligned.
+    if self.num_buffered_values > 0 {
+      assert_eq!(self.num_buffered_values, 8);
+      let bit_width = self.bit_width as usize;
+      let out = self.bit_writer.get_next_byte_ptr(bit_width)?;
+      bit_util::pack8(&self.buffered_values, bit_width, out);
     }
     self.num_buffered_values = 0;
     if update_indicator_byte {
@@ -491,10 +528,20 @@ impl RleDecoder {
     assert!(self.bit_reader.is_some());
     if let Some(ref mut bit_reader) = self.bit_reader {
       if let Some(indicator_value) = bit_reader.get_vlq_int() {
+        // Run length is read from data, run that overflows `u32` is corrupt and no more
+        // values are decoded.
         if indicator_value & 1 == 1 {
-          self.bit_packed_left = ((indicator_value >> 1) * 8) as u32;
+          let run_len = match (indicator_value >> 1).checked_mul(8) {
+            Some(run_len) if run_len as u32 as i64 == run_len => run_len,
+            _ => return false,
+          };
+          self.bit_packed_left = run_len as u32;
         } else {
-          self.rle_left = (indicator_value >> 1) as u32;
+          let run_len = indicator_value >> 1;
+          if run_len as u32 as i64 != run_len {
+            return false;
+          }
+          self.rle_left = run_len as u32;
           let value_width = bit_util::ceil(self.bit_width as i64, 8);
           self.current_value = bit_reader.get_aligned::<u64>(value_width as usize);
           assert!(self.current_value.is_some());
@@ -534,6 +581,23 @@ mod tests {
     assert_eq!(buffer, expected);
   }
 
+  #[test]
+  fn test_rle_decode_run_length_overflow() {
+    // Bit-packed run of 2^29 + 1 groups, i.e. 2^32 + 8 values, followed by one group
+    let data = ByteBufferPtr::new(vec![0x83, 0x80, 0x80, 0x80, 0x04, 0xFF]);
+    let mut decoder: RleDecoder = RleDecoder::new(1);
+    decoder.set_data(data);
+    let mut buffer = vec![0; 8];
+    assert_eq!(decoder.get_batch::<i32>(&mut buffer).unwrap(), 0);
+
+    // RLE run of 2^32 + 3 values
+    let data = ByteBufferPtr::new(vec![0x86, 0x80, 0x80, 0x80, 0x20, 0x01]);
+    let mut decoder: RleDecoder = RleDecoder::new(1);
+    decoder.set_data(data);
+    let mut buffer = vec![0; 8];
+    assert_eq!(decoder.get_batch::<i32>(&mut buffer).unwrap(), 0);
+  }
+
   #[test]
   fn test_rle_consume_flush_buffer() {
     let data = vec![1, 1, 1, 2, 2, 3, 3, 3];
@@ -770,6 +834,38 @@ mod tests {
     assert_eq!(actual_values, values);
   }
 
+  #[test]
+  fn test_rle_for_levels() {
+    let max_level = 5;
+    let mut encoder = RleEncoder::for_levels(max_level, 0);
+    let values: Vec<i16> = vec![0, 1, 2, 3, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 0, 3];
+    for v in &values {
+      assert!(encoder.put(*v as u64).expect("put() should be OK"));
+    }
+
+    // Value above max level does not fit into bit width
+    assert_eq!(
+      encoder.put(6).unwrap_err(),
+      general_err!("Value 6 exceeds maximum value 5")
+    );
+    // Negative level is rejected as well
+    assert!(encoder.put(-1i16 as u64).is_err());
+
+    let buffer = encoder.consume().expect("consume() should be OK");
+    let mut decoder = RleDecoder::new(3);
+    decoder.set_data(ByteBufferPtr::new(buffer));
+    let mut actual_values: Vec<i16> = vec![0; values.len()];
+    decoder
+      .get_batch(&mut actual_values)
+      .expect("get_batch() should be OK");
+    assert_eq!(actual_values, values);
+
+    // Encoder for max level 0 has bit width 0 and only accepts 0
+    let mut encoder = RleEncoder::for_levels(0, 0);
+    assert!(encoder.put(0).unwrap());
+    assert!(encoder.put(1).is_err());
+  }
+
   fn test_round_trip(values: &[i32], bit_width: u8) {
     let buffer_len = 64 * 1024;
     let mut encoder = RleEncoder::new(bit_width, buffer_len);
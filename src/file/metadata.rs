@@ -33,7 +33,7 @@
 //! [`ColumnChunkMetaData`](struct.ColumnChunkMetaData.html) has information about column
 //! chunk (primitive leaf column), including encoding/compression, number of values, etc.
 
-use std::rc::Rc;
+use std::{cmp, sync::Arc};
 
 use super::statistics::{self, Statistics};
 use basic::{ColumnOrder, Compression, Encoding, Type};
@@ -44,8 +44,11 @@ use schema::types::{
   Type as SchemaType, TypePtr,
 };
 
-/// Reference counted pointer for [`ParquetMetaData`].
-pub type ParquetMetaDataPtr = Rc<ParquetMetaData>;
+/// Atomically reference counted pointer for [`ParquetMetaData`].
+///
+/// Metadata is shared with `Arc` and is `Send + Sync`, so that it can be held
+/// independently of the file reader it was obtained from, including on other threads.
+pub type ParquetMetaDataPtr = Arc<ParquetMetaData>;
 
 /// Global Parquet metadata.
 pub struct ParquetMetaData {
@@ -54,7 +57,7 @@ pub struct ParquetMetaData {
 }
 
 impl ParquetMetaData {
-  /// Creates Parquet metadata from file metadata and a list of row group metadata `Rc`s
+  /// Creates Parquet metadata from file metadata and a list of row group metadata `Arc`s
   /// for each available row group.
   pub fn new(
     file_metadata: FileMetaData,
@@ -62,7 +65,7 @@ impl ParquetMetaData {
   ) -> Self
   {
     ParquetMetaData {
-      file_metadata: Rc::new(file_metadata),
+      file_metadata: Arc::new(file_metadata),
       row_groups: row_group_ptrs,
     }
   }
@@ -82,7 +85,7 @@ impl ParquetMetaData {
 }
 
 /// Reference counted pointer for [`FileMetaData`].
-pub type FileMetaDataPtr = Rc<FileMetaData>;
+pub type FileMetaDataPtr = Arc<FileMetaData>;
 
 /// Metadata for a Parquet file.
 pub struct FileMetaData {
@@ -161,7 +164,7 @@ impl FileMetaData {
 }
 
 /// Reference counted pointer for [`RowGroupMetaData`].
-pub type RowGroupMetaDataPtr = Rc<RowGroupMetaData>;
+pub type RowGroupMetaDataPtr = Arc<RowGroupMetaData>;
 
 /// Metadata for a row group.
 pub struct RowGroupMetaData {
@@ -183,10 +186,13 @@ impl RowGroupMetaData {
   /// Returns column chunk metadata for `i`th column.
   pub fn column(&self, i: usize) -> &ColumnChunkMetaData { &self.columns[i] }
 
-  /// Returns slice of column chunk metadata [`Rc`] pointers.
+  /// Returns slice of column chunk metadata [`Arc`] pointers.
   pub fn columns(&self) -> &[ColumnChunkMetaDataPtr] { &self.columns }
 
-  /// Number of rows in this row group.
+  /// Number of rows (records) in this row group.
+  ///
+  /// For repeated columns this is different from the number of values in a column
+  /// chunk, see [`ColumnChunkMetaData::num_values`].
   pub fn num_rows(&self) -> i64 { self.num_rows }
 
   /// Total byte size of all uncompressed column data in this row group.
@@ -210,7 +216,7 @@ impl RowGroupMetaData {
     let mut columns = vec![];
     for (c, d) in rg.columns.drain(0..).zip(schema_descr.columns()) {
       let cc = ColumnChunkMetaData::from_thrift(d.clone(), c)?;
-      columns.push(Rc::new(cc));
+      columns.push(Arc::new(cc));
     }
     Ok(RowGroupMetaData {
       columns,
@@ -288,7 +294,7 @@ impl RowGroupMetaDataBuilder {
 }
 
 /// Reference counted pointer for [`ColumnChunkMetaData`].
-pub type ColumnChunkMetaDataPtr = Rc<ColumnChunkMetaData>;
+pub type ColumnChunkMetaDataPtr = Arc<ColumnChunkMetaData>;
 
 /// Metadata for a column chunk.
 pub struct ColumnChunkMetaData {
@@ -339,7 +345,12 @@ impl ColumnChunkMetaData {
   /// All encodings used for this column.
   pub fn encodings(&self) -> &Vec<Encoding> { &self.encodings }
 
-  /// Total number of values in this column chunk.
+  /// Total number of values in this column chunk, including nulls.
+  ///
+  /// This is the number of leaf level entries, not records: a repeated column stores
+  /// one entry per list element (or one for an empty or null list), so it can have more
+  /// values than rows in the row group. Repetition levels map values to rows. For
+  /// non-repeated columns it is equal to the number of rows.
   pub fn num_values(&self) -> i64 { self.num_values }
 
   /// Compression for this column.
@@ -370,6 +381,32 @@ impl ColumnChunkMetaData {
   /// or `None` if no statistics are available.
   pub fn statistics(&self) -> Option<&Statistics> { self.statistics.as_ref() }
 
+  /// Returns estimated size in bytes of this column chunk once decoded into memory,
+  /// computed from metadata only.
+  ///
+  /// Fixed-width columns use `num_values` times the physical type width. Byte array
+  /// columns use total uncompressed size as an upper bound, since it includes lengths
+  /// of all values. Nullable columns add one bit per value for validity.
+  pub fn estimated_decoded_size(&self) -> usize {
+    let num_values = cmp::max(self.num_values, 0) as usize;
+    let values_size = match self.column_type {
+      Type::BOOLEAN => num_values,
+      Type::INT32 | Type::FLOAT => num_values.saturating_mul(4),
+      Type::INT64 | Type::DOUBLE => num_values.saturating_mul(8),
+      Type::INT96 => num_values.saturating_mul(12),
+      Type::FIXED_LEN_BYTE_ARRAY => {
+        num_values.saturating_mul(cmp::max(self.column_descr.type_length(), 0) as usize)
+      },
+      Type::BYTE_ARRAY => cmp::max(self.total_uncompressed_size, 0) as usize,
+    };
+    let validity_size = if self.column_descr.max_def_level() > 0 {
+      num_values.saturating_add(7) / 8
+    } else {
+      0
+    };
+    values_size.saturating_add(validity_size)
+  }
+
   /// Method to convert from Thrift.
   pub fn from_thrift(column_descr: ColumnDescPtr, cc: ColumnChunk) -> Result<Self> {
     if cc.meta_data.is_none() {
@@ -568,6 +605,14 @@ impl ColumnChunkMetaDataBuilder {
 mod tests {
   use super::*;
 
+  use basic::Repetition;
+
+  #[test]
+  fn test_metadata_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ParquetMetaData>();
+  }
+
   #[test]
   fn test_row_group_metadata_thrift_conversion() {
     let schema_descr = get_test_schema_descr();
@@ -575,7 +620,7 @@ mod tests {
     let mut columns = vec![];
     for ptr in schema_descr.columns() {
       let column = ColumnChunkMetaData::builder(ptr.clone()).build().unwrap();
-      columns.push(Rc::new(column));
+      columns.push(Arc::new(column));
     }
     let row_group_meta = RowGroupMetaData::builder(schema_descr.clone())
       .set_num_rows(1000)
@@ -652,16 +697,73 @@ mod tests {
     assert_eq!(col_chunk_res, col_chunk_exp);
   }
 
+  #[test]
+  fn test_column_chunk_metadata_estimated_decoded_size() {
+    let schema = SchemaType::group_type_builder("schema")
+      .with_fields(&mut vec![
+        Arc::new(
+          SchemaType::primitive_type_builder("a", Type::INT64)
+            .with_repetition(Repetition::REQUIRED)
+            .build()
+            .unwrap(),
+        ),
+        Arc::new(
+          SchemaType::primitive_type_builder("b", Type::FIXED_LEN_BYTE_ARRAY)
+            .with_repetition(Repetition::REQUIRED)
+            .with_length(5)
+            .build()
+            .unwrap(),
+        ),
+        Arc::new(
+          SchemaType::primitive_type_builder("c", Type::INT32)
+            .with_repetition(Repetition::OPTIONAL)
+            .build()
+            .unwrap(),
+        ),
+        Arc::new(
+          SchemaType::primitive_type_builder("d", Type::BYTE_ARRAY)
+            .with_repetition(Repetition::REQUIRED)
+            .build()
+            .unwrap(),
+        ),
+      ])
+      .build()
+      .unwrap();
+    let schema_descr = SchemaDescriptor::new(Arc::new(schema));
+
+    let estimate = |i: usize| {
+      ColumnChunkMetaData::builder(schema_descr.column(i))
+        .set_num_values(1000)
+        .set_total_uncompressed_size(12345)
+        .build()
+        .unwrap()
+        .estimated_decoded_size()
+    };
+
+    assert_eq!(estimate(0), 1000 * 8);
+    assert_eq!(estimate(1), 1000 * 5);
+    assert_eq!(estimate(2), 1000 * 4 + 125);
+    assert_eq!(estimate(3), 12345);
+
+    // Corrupt number of values does not overflow the estimate
+    let estimate = ColumnChunkMetaData::builder(schema_descr.column(2))
+      .set_num_values(i64::max_value())
+      .build()
+      .unwrap()
+      .estimated_decoded_size();
+    assert_eq!(estimate, usize::max_value());
+  }
+
   /// Returns sample schema descriptor so we can create column metadata.
   fn get_test_schema_descr() -> SchemaDescPtr {
     let schema = SchemaType::group_type_builder("schema")
       .with_fields(&mut vec![
-        Rc::new(
+        Arc::new(
           SchemaType::primitive_type_builder("a", Type::INT32)
             .build()
             .unwrap(),
         ),
-        Rc::new(
+        Arc::new(
           SchemaType::primitive_type_builder("b", Type::INT32)
             .build()
             .unwrap(),
@@ -670,6 +772,6 @@ mod tests {
       .build()
       .unwrap();
 
-    Rc::new(SchemaDescriptor::new(Rc::new(schema)))
+    Arc::new(SchemaDescriptor::new(Arc::new(schema)))
   }
 }
@@ -44,7 +44,7 @@
 //! # Example of writing a new file
 //!
 //! ```rust
-//! use std::{fs, path::Path, rc::Rc};
+//! use std::{fs, path::Path, rc::Rc, sync::Arc};
 //!
 //! use parquet::{
 //!   file::{
@@ -61,7 +61,7 @@
 //!     REQUIRED INT32 b;
 //!   }
 //! ";
-//! let schema = Rc::new(parse_message_type(message_type).unwrap());
+//! let schema = Arc::new(parse_message_type(message_type).unwrap());
 //! let props = Rc::new(WriterProperties::builder().build());
 //! let file = fs::File::create(&path).unwrap();
 //! let mut writer = SerializedFileWriter::new(file, schema, props).unwrap();
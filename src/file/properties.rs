@@ -54,6 +54,7 @@ use basic::{Compression, Encoding};
 use schema::types::ColumnPath;
 
 const DEFAULT_PAGE_SIZE: usize = 1024 * 1024;
+const DEFAULT_DATA_PAGE_ROW_COUNT_LIMIT: usize = ::std::usize::MAX;
 const DEFAULT_WRITE_BATCH_SIZE: usize = 1024;
 const DEFAULT_WRITER_VERSION: WriterVersion = WriterVersion::PARQUET_1_0;
 const DEFAULT_COMPRESSION: Compression = Compression::UNCOMPRESSED;
@@ -62,6 +63,7 @@ const DEFAULT_DICTIONARY_PAGE_SIZE_LIMIT: usize = DEFAULT_PAGE_SIZE;
 const DEFAULT_STATISTICS_ENABLED: bool = true;
 const DEFAULT_MAX_STATISTICS_SIZE: usize = 4096;
 const DEFAULT_MAX_ROW_GROUP_SIZE: usize = 128 * 1024 * 1024;
+const DEFAULT_MIN_COMPRESS_SIZE: usize = 0;
 const DEFAULT_CREATED_BY: &str = env!("PARQUET_CREATED_BY");
 
 /// Parquet writer version.
@@ -93,9 +95,11 @@ pub type WriterPropertiesPtr = Rc<WriterProperties>;
 #[derive(Debug, Clone)]
 pub struct WriterProperties {
   data_pagesize_limit: usize,
+  data_page_row_count_limit: usize,
   dictionary_pagesize_limit: usize,
   write_batch_size: usize,
   max_row_group_size: usize,
+  min_compress_size: usize,
   writer_version: WriterVersion,
   created_by: String,
   default_column_properties: ColumnProperties,
@@ -109,6 +113,12 @@ impl WriterProperties {
   /// Returns data page size limit.
   pub fn data_pagesize_limit(&self) -> usize { self.data_pagesize_limit }
 
+  /// Returns max number of rows in a data page.
+  ///
+  /// Data page is added when either this limit or data page size limit is reached,
+  /// whichever comes first.
+  pub fn data_page_row_count_limit(&self) -> usize { self.data_page_row_count_limit }
+
   /// Returns dictionary page size limit.
   pub fn dictionary_pagesize_limit(&self) -> usize { self.dictionary_pagesize_limit }
 
@@ -122,6 +132,9 @@ impl WriterProperties {
   /// Returns max size for a row group.
   pub fn max_row_group_size(&self) -> usize { self.max_row_group_size }
 
+  /// Returns minimum uncompressed size of data page values to be compressed.
+  pub fn min_compress_size(&self) -> usize { self.min_compress_size }
+
   /// Returns configured writer version.
   pub fn writer_version(&self) -> WriterVersion { self.writer_version }
 
@@ -204,9 +217,11 @@ impl WriterProperties {
 /// Writer properties builder.
 pub struct WriterPropertiesBuilder {
   data_pagesize_limit: usize,
+  data_page_row_count_limit: usize,
   dictionary_pagesize_limit: usize,
   write_batch_size: usize,
   max_row_group_size: usize,
+  min_compress_size: usize,
   writer_version: WriterVersion,
   created_by: String,
   default_column_properties: ColumnProperties,
@@ -218,9 +233,11 @@ impl WriterPropertiesBuilder {
   fn with_defaults() -> Self {
     Self {
       data_pagesize_limit: DEFAULT_PAGE_SIZE,
+      data_page_row_count_limit: DEFAULT_DATA_PAGE_ROW_COUNT_LIMIT,
       dictionary_pagesize_limit: DEFAULT_DICTIONARY_PAGE_SIZE_LIMIT,
       write_batch_size: DEFAULT_WRITE_BATCH_SIZE,
       max_row_group_size: DEFAULT_MAX_ROW_GROUP_SIZE,
+      min_compress_size: DEFAULT_MIN_COMPRESS_SIZE,
       writer_version: DEFAULT_WRITER_VERSION,
       created_by: DEFAULT_CREATED_BY.to_string(),
       default_column_properties: ColumnProperties::new(),
@@ -232,9 +249,11 @@ impl WriterPropertiesBuilder {
   pub fn build(self) -> WriterProperties {
     WriterProperties {
       data_pagesize_limit: self.data_pagesize_limit,
+      data_page_row_count_limit: self.data_page_row_count_limit,
       dictionary_pagesize_limit: self.dictionary_pagesize_limit,
       write_batch_size: self.write_batch_size,
       max_row_group_size: self.max_row_group_size,
+      min_compress_size: self.min_compress_size,
       writer_version: self.writer_version,
       created_by: self.created_by,
       default_column_properties: self.default_column_properties,
@@ -257,6 +276,18 @@ impl WriterPropertiesBuilder {
     self
   }
 
+  /// Sets max number of rows in a data page.
+  ///
+  /// Limit is exact for non-repeated columns. For repeated columns it is checked after
+  /// each write batch, so a data page can exceed it by up to write batch size rows.
+  ///
+  /// Panics if value is 0.
+  pub fn set_data_page_row_count_limit(mut self, value: usize) -> Self {
+    assert!(value > 0, "Data page row count limit must be greater than 0");
+    self.data_page_row_count_limit = value;
+    self
+  }
+
   /// Sets dictionary page size limit.
   pub fn set_dictionary_pagesize_limit(mut self, value: usize) -> Self {
     self.dictionary_pagesize_limit = value;
@@ -275,6 +306,18 @@ impl WriterPropertiesBuilder {
     self
   }
 
+  /// Sets minimum uncompressed size of data page values to be compressed, by default
+  /// all pages are compressed. Repetition and definition levels are never compressed
+  /// in data page v2, so their size is not included.
+  ///
+  /// Compression codec is set per column chunk, only data page v2 can mark individual
+  /// pages as uncompressed. Therefore this applies to `PARQUET_2_0` writer version only,
+  /// data pages v1 and dictionary pages are always compressed with the column codec.
+  pub fn set_min_compress_size(mut self, value: usize) -> Self {
+    self.min_compress_size = value;
+    self
+  }
+
   /// Sets "created by" property.
   pub fn set_created_by(mut self, value: String) -> Self {
     self.created_by = value;
@@ -473,12 +516,17 @@ mod tests {
   fn test_writer_properties_default_settings() {
     let props = WriterProperties::builder().build();
     assert_eq!(props.data_pagesize_limit(), DEFAULT_PAGE_SIZE);
+    assert_eq!(
+      props.data_page_row_count_limit(),
+      DEFAULT_DATA_PAGE_ROW_COUNT_LIMIT
+    );
     assert_eq!(
       props.dictionary_pagesize_limit(),
       DEFAULT_DICTIONARY_PAGE_SIZE_LIMIT
     );
     assert_eq!(props.write_batch_size(), DEFAULT_WRITE_BATCH_SIZE);
     assert_eq!(props.max_row_group_size(), DEFAULT_MAX_ROW_GROUP_SIZE);
+    assert_eq!(props.min_compress_size(), DEFAULT_MIN_COMPRESS_SIZE);
     assert_eq!(props.writer_version(), DEFAULT_WRITER_VERSION);
     assert_eq!(props.created_by(), DEFAULT_CREATED_BY);
     assert_eq!(props.encoding(&ColumnPath::from("col")), None);
@@ -552,15 +600,23 @@ mod tests {
       .build();
   }
 
+  #[test]
+  #[should_panic(expected = "Data page row count limit must be greater than 0")]
+  fn test_writer_properties_panic_when_row_count_limit_is_zero() {
+    WriterProperties::builder().set_data_page_row_count_limit(0);
+  }
+
   #[test]
   fn test_writer_properties_builder() {
     let props = WriterProperties::builder()
       // file settings
       .set_writer_version(WriterVersion::PARQUET_2_0)
       .set_data_pagesize_limit(10)
+      .set_data_page_row_count_limit(15)
       .set_dictionary_pagesize_limit(20)
       .set_write_batch_size(30)
       .set_max_row_group_size(40)
+      .set_min_compress_size(45)
       .set_created_by("default".to_owned())
       // global column settings
       .set_encoding(Encoding::DELTA_BINARY_PACKED)
@@ -578,9 +634,11 @@ mod tests {
 
     assert_eq!(props.writer_version(), WriterVersion::PARQUET_2_0);
     assert_eq!(props.data_pagesize_limit(), 10);
+    assert_eq!(props.data_page_row_count_limit(), 15);
     assert_eq!(props.dictionary_pagesize_limit(), 20);
     assert_eq!(props.write_batch_size(), 30);
     assert_eq!(props.max_row_group_size(), 40);
+    assert_eq!(props.min_compress_size(), 45);
     assert_eq!(props.created_by(), "default");
 
     assert_eq!(
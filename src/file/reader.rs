@@ -19,29 +19,41 @@
 //! readers to read individual column chunks, or access record iterator.
 
 use std::{
+  cell::RefCell,
+  cmp,
+  collections::HashSet,
   convert::TryFrom,
   fs::File,
   io::{BufReader, Cursor, Read, Seek, SeekFrom},
   path::Path,
-  rc::Rc,
+  sync::Arc,
 };
 
-use basic::{ColumnOrder, Compression, Encoding, Type};
+use basic::{ColumnOrder, Compression, Encoding, SortOrder, Type};
 use byteorder::{ByteOrder, LittleEndian};
 use column::{
   page::{Page, PageReader},
   reader::{ColumnReader, ColumnReaderImpl},
 };
 use compression::{create_codec, Codec};
+use data_type::DataType;
+use encodings::decoding::{is_decoding_supported, Decoder, PlainDecoder};
 use errors::{ParquetError, Result};
-use file::{metadata::*, statistics, FOOTER_SIZE, PARQUET_MAGIC};
+use file::{
+  metadata::*,
+  statistics::{self, Statistics},
+  FOOTER_SIZE, PARQUET_MAGIC,
+};
 use parquet_format::{
   ColumnOrder as TColumnOrder, FileMetaData as TFileMetaData, PageHeader, PageType,
 };
-use record::reader::RowIter;
+use record::{reader::RowIter, Row};
 use schema::types::{self, SchemaDescriptor, Type as SchemaType};
 use thrift::protocol::TCompactInputProtocol;
-use util::{io::FileSource, memory::ByteBufferPtr};
+use util::{
+  io::{CachedFileSource, FileSource},
+  memory::ByteBufferPtr,
+};
 
 // ----------------------------------------------------------------------
 // APIs for file & row group readers
@@ -50,6 +62,7 @@ use util::{io::FileSource, memory::ByteBufferPtr};
 /// Parquet file, can get reader for each row group, and access record iterator.
 pub trait FileReader {
   /// Get metadata information about this file.
+  /// Returned pointer is a cheap clone and can be kept after the reader is dropped.
   fn metadata(&self) -> ParquetMetaDataPtr;
 
   /// Get the total number of row groups for this file.
@@ -89,6 +102,98 @@ pub trait RowGroupReader {
   fn get_row_iter(&self, projection: Option<SchemaType>) -> Result<RowIter>;
 }
 
+impl RowGroupReader {
+  /// Reads and decodes dictionary page of the `i`th column chunk without reading any
+  /// data pages.
+  ///
+  /// Returns `None` if column chunk is not dictionary encoded, which is decided from
+  /// column chunk metadata without any IO. `T` must match physical type of the column.
+  pub fn read_dictionary<T: DataType>(&self, i: usize) -> Result<Option<Vec<T::T>>> {
+    let metadata = self.metadata();
+    let col_descr = metadata.column(i).column_descr_ptr();
+    if col_descr.physical_type() != T::get_physical_type() {
+      return Err(general_err!(
+        "Column {} has physical type {}, but {} was requested",
+        i,
+        col_descr.physical_type(),
+        T::get_physical_type()
+      ));
+    }
+
+    // Column chunk metadata tells whether there is a dictionary page, so no IO is needed
+    if !metadata.column(i).has_dictionary_page() {
+      return Ok(None);
+    }
+
+    // Dictionary page, if present, is always the first page of a column chunk
+    let page = match self.get_column_page_reader(i)?.get_next_page()? {
+      Some(page @ Page::DictionaryPage { .. }) => page,
+      _ => return Ok(None),
+    };
+    match page.encoding() {
+      Encoding::PLAIN | Encoding::PLAIN_DICTIONARY => {},
+      encoding => {
+        return Err(nyi_err!(
+          "Invalid/Unsupported encoding type for dictionary: {}",
+          encoding
+        ))
+      },
+    }
+
+    // Number of values comes from page header, check that the decompressed page can
+    // hold that many values before allocating them. Every value takes at least this
+    // many bits in PLAIN encoding, byte arrays are prefixed with 4 bytes of length.
+    let min_value_bits = match col_descr.physical_type() {
+      Type::BOOLEAN => 1,
+      Type::INT32 | Type::FLOAT | Type::BYTE_ARRAY => 32,
+      Type::INT64 | Type::DOUBLE => 64,
+      Type::INT96 => 96,
+      Type::FIXED_LEN_BYTE_ARRAY => cmp::max(col_descr.type_length(), 1) as usize * 8,
+    };
+    let max_num_values = page.buffer().len() * 8 / min_value_bits;
+    let num_values = page.num_values() as usize;
+    if num_values > max_num_values {
+      return Err(general_err!(
+        "Dictionary page has {} values, but {} bytes can hold at most {} values",
+        num_values,
+        page.buffer().len(),
+        max_num_values
+      ));
+    }
+
+    let mut decoder = PlainDecoder::<T>::new(col_descr.type_length());
+    decoder.set_data(page.buffer().clone(), num_values)?;
+    let mut values = vec![T::T::default(); num_values];
+    let values_read = decoder.get(&mut values)?;
+    if values_read != num_values {
+      return Err(eof_err!(
+        "Dictionary page has {} values, but only {} could be decoded",
+        num_values,
+        values_read
+      ));
+    }
+    Ok(Some(values))
+  }
+
+  /// Returns `true` if values of the `i`th column chunk can be decoded by this crate,
+  /// see [`is_column_chunk_supported`].
+  pub fn is_column_supported(&self, i: usize) -> bool {
+    is_column_chunk_supported(self.metadata().column(i))
+  }
+}
+
+/// Returns `true` if values of column chunk can be decoded by this crate.
+///
+/// Support is decided from encodings listed in column chunk metadata, no pages are read.
+/// RLE and BIT_PACKED are skipped, because they are also listed for definition and
+/// repetition levels, which are always supported.
+pub fn is_column_chunk_supported(column: &ColumnChunkMetaData) -> bool {
+  column.encodings().iter().all(|&encoding| match encoding {
+    Encoding::RLE | Encoding::BIT_PACKED => true,
+    encoding => is_decoding_supported(encoding, column.column_type()),
+  })
+}
+
 // ----------------------------------------------------------------------
 // Serialized impl for file & row group readers
 
@@ -128,31 +233,193 @@ impl<'a> TryClone for Cursor<&'a [u8]> {
 pub trait ParquetReader: Read + Seek + Length + TryClone {}
 impl<T: Read + Seek + Length + TryClone> ParquetReader for T {}
 
+/// Default maximum number of trailing bytes scanned to find footer magic.
+pub const DEFAULT_MAX_TRAILING_BYTES: usize = 1024;
+
+/// Options that control how [`SerializedFileReader`] opens a Parquet file.
+#[derive(Clone, Debug)]
+pub struct ReadOptions {
+  tolerate_trailing_bytes: bool,
+  max_trailing_bytes: usize,
+  skip_unsupported_columns: bool,
+  cache_column_chunks: bool,
+}
+
+impl ReadOptions {
+  /// Returns builder for read options with default values.
+  pub fn builder() -> ReadOptionsBuilder { ReadOptionsBuilder::with_defaults() }
+
+  /// Returns `true` if bytes after the footer magic are skipped when opening a file.
+  pub fn tolerate_trailing_bytes(&self) -> bool { self.tolerate_trailing_bytes }
+
+  /// Returns maximum number of trailing bytes to skip when searching for the footer.
+  pub fn max_trailing_bytes(&self) -> usize { self.max_trailing_bytes }
+
+  /// Returns `true` if columns with unsupported encodings are left out of row iteration.
+  pub fn skip_unsupported_columns(&self) -> bool { self.skip_unsupported_columns }
+
+  /// Returns `true` if row group readers keep bytes of column chunks they have read.
+  pub fn cache_column_chunks(&self) -> bool { self.cache_column_chunks }
+}
+
+impl Default for ReadOptions {
+  fn default() -> Self { ReadOptions::builder().build() }
+}
+
+/// Read options builder.
+pub struct ReadOptionsBuilder {
+  tolerate_trailing_bytes: bool,
+  max_trailing_bytes: usize,
+  skip_unsupported_columns: bool,
+  cache_column_chunks: bool,
+}
+
+impl ReadOptionsBuilder {
+  /// Returns default state of the builder.
+  fn with_defaults() -> Self {
+    Self {
+      tolerate_trailing_bytes: false,
+      max_trailing_bytes: DEFAULT_MAX_TRAILING_BYTES,
+      skip_unsupported_columns: false,
+      cache_column_chunks: false,
+    }
+  }
+
+  /// Finalizes the configuration and returns immutable read options struct.
+  pub fn build(self) -> ReadOptions {
+    ReadOptions {
+      tolerate_trailing_bytes: self.tolerate_trailing_bytes,
+      max_trailing_bytes: self.max_trailing_bytes,
+      skip_unsupported_columns: self.skip_unsupported_columns,
+      cache_column_chunks: self.cache_column_chunks,
+    }
+  }
+
+  /// Sets whether file can have trailing bytes after the footer magic.
+  ///
+  /// When enabled and the file does not end with magic bytes, the reader scans backward
+  /// from the end of the file for the footer. Disabled by default, since trailing bytes
+  /// usually indicate a corrupt file.
+  pub fn set_tolerate_trailing_bytes(mut self, value: bool) -> Self {
+    self.tolerate_trailing_bytes = value;
+    self
+  }
+
+  /// Sets maximum number of trailing bytes to scan when searching for the footer.
+  pub fn set_max_trailing_bytes(mut self, value: usize) -> Self {
+    self.max_trailing_bytes = value;
+    self
+  }
+
+  /// Sets whether columns with encodings this crate cannot decode are skipped.
+  ///
+  /// When enabled, row iterator over the full file schema leaves out top-level fields
+  /// that contain such columns, so the rest of the file can still be read. Column
+  /// readers for skipped columns are still available and return an error once values
+  /// are read. Explicit projections are not changed. Disabled by default.
+  pub fn set_skip_unsupported_columns(mut self, value: bool) -> Self {
+    self.skip_unsupported_columns = value;
+    self
+  }
+
+  /// Sets whether row group readers keep bytes of column chunks in memory.
+  ///
+  /// Column chunks are always read lazily, only when values of a column are read. When
+  /// enabled, bytes read from a column chunk stay in memory for the lifetime of the row
+  /// group reader, so reading the same column again, e.g. after `read_dictionary`, does
+  /// no IO. This costs up to the compressed size of every column chunk that is read.
+  /// Disabled by default.
+  pub fn set_cache_column_chunks(mut self, value: bool) -> Self {
+    self.cache_column_chunks = value;
+    self
+  }
+}
+
 /// A serialized implementation for Parquet [`FileReader`].
 pub struct SerializedFileReader<R: ParquetReader> {
   buf: BufReader<R>,
   metadata: ParquetMetaDataPtr,
+  // Position of file metadata in the file, i.e. end of the data section.
+  metadata_offset: u64,
+  options: ReadOptions,
 }
 
 impl<R: ParquetReader> SerializedFileReader<R> {
   /// Creates file reader from a Parquet file.
   /// Returns error if Parquet file does not exist or is corrupt.
   pub fn new(reader: R) -> Result<Self> {
+    Self::new_with_options(reader, &ReadOptions::default())
+  }
+
+  /// Creates file reader from a Parquet file using provided read options.
+  /// Returns error if Parquet file does not exist or is corrupt.
+  pub fn new_with_options(reader: R, options: &ReadOptions) -> Result<Self> {
     let mut buf = BufReader::new(reader);
-    let metadata = Self::parse_metadata(&mut buf)?;
+    let (metadata, metadata_offset) = Self::parse_metadata(&mut buf, options)?;
     Ok(Self {
       buf,
-      metadata: Rc::new(metadata),
+      metadata: Arc::new(metadata),
+      metadata_offset,
+      options: options.clone(),
     })
   }
 
+  /// Returns statistics for a leaf column `col` aggregated across all row groups,
+  /// using only column chunk metadata, no pages are read.
+  ///
+  /// Returns `None` if the file has no row groups, if any of the column chunks does
+  /// not have statistics, or if statistics cannot be merged, e.g. because sort order
+  /// of the column is undefined. Returns `Err` if column index is out of bound.
+  pub fn column_statistics(&self, col: usize) -> Result<Option<Statistics>> {
+    let file_metadata = self.metadata.file_metadata();
+    let schema_descr = file_metadata.schema_descr();
+    let num_columns = schema_descr.num_columns();
+    if col >= num_columns {
+      return Err(general_err!(
+        "Column index {} out of bound, number of columns: {}",
+        col,
+        num_columns
+      ));
+    }
+    let col_descr = schema_descr.column(col);
+    let sort_order =
+      ColumnOrder::get_sort_order(col_descr.logical_type(), col_descr.physical_type());
+
+    let mut result: Option<Statistics> = None;
+    for row_group in self.metadata.row_groups() {
+      let stats = match row_group.column(col).statistics() {
+        Some(stats) => stats,
+        None => return Ok(None),
+      };
+      result = match result {
+        Some(merged) => match merged.merge(stats, sort_order) {
+          Some(merged) => Some(merged),
+          None => return Ok(None),
+        },
+        // INT96 values are compared as timestamps, see `Statistics::merge`
+        None if sort_order == SortOrder::UNDEFINED
+          && col_descr.physical_type() != Type::INT96 =>
+        {
+          return Ok(None)
+        },
+        None => Some(stats.clone()),
+      };
+    }
+    Ok(result)
+  }
+
   // Layout of Parquet file
   // +---------------------------+---+-----+
   // |      Rest of file         | B |  A  |
   // +---------------------------+---+-----+
   // where A: parquet footer, B: parquet metadata.
   //
-  fn parse_metadata(buf: &mut BufReader<R>) -> Result<ParquetMetaData> {
+  // Returns parsed metadata and position of B in the file.
+  fn parse_metadata(
+    buf: &mut BufReader<R>,
+    options: &ReadOptions,
+  ) -> Result<(ParquetMetaData, u64)>
+  {
     let file_size = buf.get_ref().len();
     if file_size < (FOOTER_SIZE as u64) {
       return Err(general_err!(
@@ -162,8 +429,14 @@ impl<R: ParquetReader> SerializedFileReader<R> {
     let mut footer_buffer: [u8; FOOTER_SIZE] = [0; FOOTER_SIZE];
     buf.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))?;
     buf.read_exact(&mut footer_buffer)?;
+    let mut footer_end = file_size;
     if footer_buffer[4..] != PARQUET_MAGIC {
-      return Err(general_err!("Invalid Parquet file. Corrupt footer"));
+      if !options.tolerate_trailing_bytes() {
+        return Err(general_err!("Invalid Parquet file. Corrupt footer"));
+      }
+      footer_end = Self::find_footer_end(buf, file_size, options.max_trailing_bytes())?;
+      buf.seek(SeekFrom::Start(footer_end - FOOTER_SIZE as u64))?;
+      buf.read_exact(&mut footer_buffer)?;
     }
     let metadata_len = LittleEndian::read_i32(&footer_buffer[0..4]) as i64;
     if metadata_len < 0 {
@@ -172,7 +445,7 @@ impl<R: ParquetReader> SerializedFileReader<R> {
         metadata_len
       ));
     }
-    let metadata_start: i64 = file_size as i64 - FOOTER_SIZE as i64 - metadata_len;
+    let metadata_start: i64 = footer_end as i64 - FOOTER_SIZE as i64 - metadata_len;
     if metadata_start < 0 {
       return Err(general_err!(
         "Invalid Parquet file. Metadata start is less than zero ({})",
@@ -188,10 +461,20 @@ impl<R: ParquetReader> SerializedFileReader<R> {
       TFileMetaData::read_from_in_protocol(&mut prot)
         .map_err(|e| ParquetError::General(format!("Could not parse metadata: {}", e)))?;
     let schema = types::from_thrift(&mut t_file_metadata.schema)?;
-    let schema_descr = Rc::new(SchemaDescriptor::new(schema.clone()));
+    let schema_descr = Arc::new(SchemaDescriptor::new(schema.clone()));
     let mut row_groups = Vec::new();
-    for rg in t_file_metadata.row_groups {
-      row_groups.push(Rc::new(RowGroupMetaData::from_thrift(
+    for (i, rg) in t_file_metadata.row_groups.into_iter().enumerate() {
+      // Column chunks are matched to schema leaves by position, check count before
+      // conversion, so that missing or surplus column chunks are not dropped.
+      if rg.columns.len() != schema_descr.num_columns() {
+        return Err(general_err!(
+          "Invalid Parquet file. Row group {} has {} column chunks, expected {}",
+          i,
+          rg.columns.len(),
+          schema_descr.num_columns()
+        ));
+      }
+      row_groups.push(Arc::new(RowGroupMetaData::from_thrift(
         schema_descr.clone(),
         rg,
       )?));
@@ -207,7 +490,36 @@ impl<R: ParquetReader> SerializedFileReader<R> {
       schema_descr,
       column_orders,
     );
-    Ok(ParquetMetaData::new(file_metadata, row_groups))
+    Ok((
+      ParquetMetaData::new(file_metadata, row_groups),
+      metadata_start as u64,
+    ))
+  }
+
+  /// Scans at most `max_trailing_bytes` bytes backward from the end of the file for the
+  /// footer magic and returns position right after it.
+  fn find_footer_end(
+    buf: &mut BufReader<R>,
+    file_size: u64,
+    max_trailing_bytes: usize,
+  ) -> Result<u64>
+  {
+    let tail_len = cmp::min(file_size, (max_trailing_bytes + FOOTER_SIZE) as u64);
+    let tail_start = file_size - tail_len;
+    let mut tail = vec![0; tail_len as usize];
+    buf.seek(SeekFrom::Start(tail_start))?;
+    buf.read_exact(&mut tail)?;
+
+    // Footer is metadata length followed by magic, it cannot end before `FOOTER_SIZE`
+    for end in (FOOTER_SIZE..tail.len()).rev() {
+      if tail[end - PARQUET_MAGIC.len()..end] == PARQUET_MAGIC {
+        return Ok(tail_start + end as u64);
+      }
+    }
+    Err(general_err!(
+      "Invalid Parquet file. Footer magic not found in last {} bytes",
+      max_trailing_bytes
+    ))
   }
 
   /// Parses column orders from Thrift definition.
@@ -244,6 +556,60 @@ impl<R: ParquetReader> SerializedFileReader<R> {
   }
 }
 
+impl<R: 'static + ParquetReader> SerializedFileReader<R> {
+  /// Reads at most `limit` rows from the file, using optional `projection`.
+  ///
+  /// Rows are complete records, so for nested columns all values of a record are read
+  /// before it is returned. Row groups and pages are read lazily, reading stops as soon
+  /// as `limit` rows are produced, even in the middle of a row group.
+  pub fn read_rows_limit(
+    &self,
+    projection: Option<SchemaType>,
+    limit: usize,
+  ) -> Result<Vec<Row>>
+  {
+    if limit == 0 {
+      return Ok(Vec::new());
+    }
+    Ok(self.get_row_iter(projection)?.take(limit).collect())
+  }
+
+  /// Returns indices of leaf columns that cannot be decoded by this crate in at least
+  /// one row group, see [`is_column_chunk_supported`].
+  pub fn unsupported_columns(&self) -> Vec<usize> {
+    let num_columns = self.metadata.file_metadata().schema_descr().num_columns();
+    let row_groups = self.metadata.row_groups();
+    (0..num_columns)
+      .filter(|&i| {
+        row_groups
+          .iter()
+          .any(|row_group| !is_column_chunk_supported(row_group.column(i)))
+      })
+      .collect()
+  }
+
+  /// Returns file schema without top-level fields that contain unsupported columns.
+  fn supported_projection(&self) -> Result<SchemaType> {
+    let file_metadata = self.metadata.file_metadata();
+    let schema_descr = file_metadata.schema_descr();
+    let unsupported_roots = self
+      .unsupported_columns()
+      .into_iter()
+      .map(|i| schema_descr.get_column_root(i).name().to_owned())
+      .collect::<HashSet<_>>();
+    let root_schema = schema_descr.root_schema();
+    let mut fields = root_schema
+      .get_fields()
+      .iter()
+      .filter(|field| !unsupported_roots.contains(field.name()))
+      .cloned()
+      .collect::<Vec<_>>();
+    SchemaType::group_type_builder(root_schema.name())
+      .with_fields(&mut fields)
+      .build()
+  }
+}
+
 impl<R: 'static + ParquetReader> FileReader for SerializedFileReader<R> {
   fn metadata(&self) -> ParquetMetaDataPtr { self.metadata.clone() }
 
@@ -256,10 +622,17 @@ impl<R: 'static + ParquetReader> FileReader for SerializedFileReader<R> {
     Ok(Box::new(SerializedRowGroupReader::new(
       f,
       row_group_metadata,
+      self.options.cache_column_chunks(),
     )))
   }
 
   fn get_row_iter(&self, projection: Option<SchemaType>) -> Result<RowIter> {
+    let projection = match projection {
+      None if self.options.skip_unsupported_columns() => {
+        Some(self.supported_projection()?)
+      },
+      projection => projection,
+    };
     RowIter::from_file(projection, self)
   }
 }
@@ -291,17 +664,150 @@ impl<'a> TryFrom<&'a str> for SerializedFileReader<File> {
   fn try_from(path: &str) -> Result<Self> { Self::try_from(Path::new(&path)) }
 }
 
+/// Checks structural invariants of the file layout and returns list of problems
+/// found, the list is empty if no problems are found.
+///
+/// The following is checked:
+/// - column chunks of every row group are in schema order, number of column chunks is
+///   already checked when the reader is created;
+/// - column chunks are stored in order and do not overlap;
+/// - column chunks fit into the data section of the file, between leading magic bytes
+///   and file metadata;
+/// - non-repeated column chunks have one value per row, repeated column chunks have at
+///   least one value per row;
+/// - sum of row group row counts matches number of rows in file metadata.
+///
+/// This is a diagnostic tool for files written by other implementations, all checks are
+/// performed and reported together instead of failing on the first one. Only metadata
+/// already parsed by the reader is used, the file is not read again.
+pub fn validate_layout<R: ParquetReader>(
+  reader: &SerializedFileReader<R>,
+) -> Result<Vec<String>> {
+  let mut problems = Vec::new();
+  let file_metadata = reader.metadata.file_metadata();
+
+  // Column chunks must end before file metadata starts.
+  let data_end = reader.metadata_offset as i64;
+
+  let mut prev_end = PARQUET_MAGIC.len() as i64;
+  let mut total_num_rows = 0;
+  for (i, row_group) in reader.metadata.row_groups().iter().enumerate() {
+    total_num_rows += row_group.num_rows();
+
+    for (j, column) in row_group.columns().iter().enumerate() {
+      if column.column_path() != column.column_descr().path() {
+        problems.push(format!(
+          "Row group {}, column {}: path {} does not match schema leaf {}",
+          i,
+          j,
+          column.column_path(),
+          column.column_descr().path()
+        ));
+      }
+
+      // Every row has one leaf value in non-repeated columns and at least one in
+      // repeated columns, repetition levels define row boundaries.
+      let is_repeated = column.column_descr().max_rep_level() > 0;
+      if !is_repeated && column.num_values() != row_group.num_rows() {
+        problems.push(format!(
+          "Row group {}, column {}: {} values do not match {} rows",
+          i,
+          j,
+          column.num_values(),
+          row_group.num_rows()
+        ));
+      }
+      if is_repeated && column.num_values() < row_group.num_rows() {
+        problems.push(format!(
+          "Row group {}, column {}: {} values are fewer than {} rows",
+          i,
+          j,
+          column.num_values(),
+          row_group.num_rows()
+        ));
+      }
+
+      // Chunk starts with dictionary page, if there is one.
+      let start = column
+        .dictionary_page_offset()
+        .filter(|&offset| offset > 0)
+        .map_or(column.data_page_offset(), |offset| {
+          cmp::min(offset, column.data_page_offset())
+        });
+      let end = start + column.compressed_size();
+      if column.compressed_size() < 0 {
+        problems.push(format!(
+          "Row group {}, column {}: negative compressed size {}",
+          i,
+          j,
+          column.compressed_size()
+        ));
+      }
+      if start < prev_end {
+        problems.push(format!(
+          "Row group {}, column {}: chunk at offset {} overlaps previous data ending \
+           at {}",
+          i, j, start, prev_end
+        ));
+      }
+      if end > data_end {
+        problems.push(format!(
+          "Row group {}, column {}: chunk ends at {} past the end of data at {}",
+          i, j, end, data_end
+        ));
+      }
+      prev_end = cmp::max(prev_end, end);
+    }
+  }
+
+  if total_num_rows != file_metadata.num_rows() {
+    problems.push(format!(
+      "Sum of row group rows {} does not match file num_rows {}",
+      total_num_rows,
+      file_metadata.num_rows()
+    ));
+  }
+
+  Ok(problems)
+}
+
 /// A serialized implementation for Parquet [`RowGroupReader`].
 pub struct SerializedRowGroupReader<R: ParquetReader> {
   buf: BufReader<R>,
   metadata: RowGroupMetaDataPtr,
+  // Cached column chunk sources, created on first access to each column and shared by
+  // all readers of that column. Not set when caching is disabled.
+  column_chunks: Option<RefCell<Vec<Option<CachedFileSource<R>>>>>,
 }
 
 impl<R: 'static + ParquetReader> SerializedRowGroupReader<R> {
   /// Creates new row group reader from a file and row group metadata.
-  fn new(file: R, metadata: RowGroupMetaDataPtr) -> Self {
+  /// When `cache_column_chunks` is set, bytes read from column chunks are kept in memory,
+  /// see [`ReadOptionsBuilder::set_cache_column_chunks`].
+  fn new(file: R, metadata: RowGroupMetaDataPtr, cache_column_chunks: bool) -> Self {
     let buf = BufReader::new(file);
-    Self { buf, metadata }
+    let column_chunks = if cache_column_chunks {
+      Some(RefCell::new(vec![None; metadata.num_columns()]))
+    } else {
+      None
+    };
+    Self {
+      buf,
+      metadata,
+      column_chunks,
+    }
+  }
+
+  /// Returns start position and length of the `i`th column chunk in the file.
+  // TODO: fix PARQUET-816
+  fn column_chunk_range(&self, i: usize) -> (u64, usize) {
+    let col = self.metadata.column(i);
+    let mut col_start = col.data_page_offset();
+    if col.has_dictionary_page() {
+      col_start = col.dictionary_page_offset().unwrap();
+    }
+    let col_length = col.compressed_size();
+    (col_start as u64, col_length as usize)
   }
 }
 
@@ -310,23 +816,32 @@ impl<R: 'static + ParquetReader> RowGroupReader for SerializedRowGroupReader<R>
 
   fn num_columns(&self) -> usize { self.metadata.num_columns() }
 
-  // TODO: fix PARQUET-816
   fn get_column_page_reader(&self, i: usize) -> Result<Box<PageReader>> {
     let col = self.metadata.column(i);
-    let mut col_start = col.data_page_offset();
-    if col.has_dictionary_page() {
-      col_start = col.dictionary_page_offset().unwrap();
+    let (col_start, col_length) = self.column_chunk_range(i);
+    let num_values = col.num_values();
+    let codec = col.compression();
+    let physical_type = col.column_descr().physical_type();
+    match self.column_chunks {
+      Some(ref column_chunks) => {
+        let mut column_chunks = column_chunks.borrow_mut();
+        if column_chunks[i].is_none() {
+          let file_chunk =
+            CachedFileSource::new(self.buf.get_ref(), col_start, col_length)?;
+          column_chunks[i] = Some(file_chunk);
+        }
+        let file_chunk = column_chunks[i].as_ref().unwrap().clone();
+        let page_reader =
+          SerializedPageReader::new(file_chunk, num_values, codec, physical_type)?;
+        Ok(Box::new(page_reader))
+      },
+      None => {
+        let file_chunk = FileSource::new(self.buf.get_ref(), col_start, col_length);
+        let page_reader =
+          SerializedPageReader::new(file_chunk, num_values, codec, physical_type)?;
+        Ok(Box::new(page_reader))
+      },
     }
-    let col_length = col.compressed_size();
-    let file_chunk =
-      FileSource::new(self.buf.get_ref(), col_start as u64, col_length as usize);
-    let page_reader = SerializedPageReader::new(
-      file_chunk,
-      col.num_values(),
-      col.compression(),
-      col.column_descr().physical_type(),
-    )?;
-    Ok(Box::new(page_reader))
   }
 
   fn get_column_reader(&self, i: usize) -> Result<ColumnReader> {
@@ -414,6 +929,37 @@ impl<T: Read> SerializedPageReader<T> {
     let page_header = PageHeader::read_from_in_protocol(&mut prot)?;
     Ok(page_header)
   }
+
+  /// Returns the next page header together with the page body exactly as it is stored
+  /// in the file, i.e. still compressed, or `None` if there are no pages left.
+  ///
+  /// Unlike `get_next_page`, pages of any type are returned, and nothing is decoded.
+  /// Result can be passed to [`PageWriter::write_raw_page`] to copy the page into
+  /// another file without re-encoding.
+  pub fn next_raw(&mut self) -> Result<Option<(PageHeader, ByteBufferPtr)>> {
+    if self.seen_num_values >= self.total_num_values {
+      return Ok(None);
+    }
+
+    let page_header = self.read_page_header()?;
+    if page_header.compressed_page_size < 0 {
+      return Err(general_err!(
+        "Invalid page header, compressed page size is negative ({})",
+        page_header.compressed_page_size
+      ));
+    }
+    let mut buffer = vec![0; page_header.compressed_page_size as usize];
+    self.buf.read_exact(&mut buffer)?;
+
+    if let Some(ref header) = page_header.data_page_header {
+      self.seen_num_values += header.num_values as i64;
+    }
+    if let Some(ref header) = page_header.data_page_header_v2 {
+      self.seen_num_values += header.num_values as i64;
+    }
+
+    Ok(Some((page_header, ByteBufferPtr::new(buffer))))
+  }
 }
 
 impl<T: Read> PageReader for SerializedPageReader<T> {
@@ -524,9 +1070,20 @@ impl<T: Read> PageReader for SerializedPageReader<T> {
 
 #[cfg(test)]
 mod tests {
+  use std::rc::Rc;
+
   use super::*;
-  use basic::SortOrder;
+  use basic::{Repetition, SortOrder};
+  use column::writer::ColumnWriter;
+  use data_type::{ByteArray, ByteArrayType, Int32Type, Int64Type};
+  use file::{
+    properties::WriterProperties,
+    writer::{FileWriter, SerializedFileWriter},
+  };
   use parquet_format::TypeDefinedOrder;
+  use record::RowAccessor;
+  use schema::types::{ColumnPath, TypePtr};
+  use thrift::protocol::TCompactOutputProtocol;
   use util::test_common::{get_temp_file, get_test_file, get_test_path};
 
   #[test]
@@ -567,6 +1124,40 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_file_reader_trailing_bytes() {
+    let mut data = include_bytes!("../../data/alltypes_plain.parquet").to_vec();
+    data.extend_from_slice(&[0; 16]);
+
+    let file = get_temp_file("trailing-bytes.parquet", &data);
+
+    let reader_result = SerializedFileReader::new(file.try_clone().unwrap());
+    assert_eq!(
+      reader_result.err().unwrap(),
+      general_err!("Invalid Parquet file. Corrupt footer")
+    );
+
+    let options = ReadOptions::builder()
+      .set_tolerate_trailing_bytes(true)
+      .build();
+    let reader =
+      SerializedFileReader::new_with_options(file.try_clone().unwrap(), &options)
+        .unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 8);
+    assert_eq!(reader.get_row_iter(None).unwrap().count(), 8);
+
+    // Trailing bytes exceed maximum scan distance
+    let options = ReadOptions::builder()
+      .set_tolerate_trailing_bytes(true)
+      .set_max_trailing_bytes(8)
+      .build();
+    let reader_result = SerializedFileReader::new_with_options(file, &options);
+    assert_eq!(
+      reader_result.err().unwrap(),
+      general_err!("Invalid Parquet file. Footer magic not found in last 8 bytes")
+    );
+  }
+
   #[test]
   fn test_file_reader_metadata_invalid_length() {
     let test_file =
@@ -595,12 +1186,12 @@ mod tests {
   fn test_file_reader_column_orders_parse() {
     // Define simple schema, we do not need to provide logical types.
     let mut fields = vec![
-      Rc::new(
+      Arc::new(
         SchemaType::primitive_type_builder("col1", Type::INT32)
           .build()
           .unwrap(),
       ),
-      Rc::new(
+      Arc::new(
         SchemaType::primitive_type_builder("col2", Type::FLOAT)
           .build()
           .unwrap(),
@@ -610,7 +1201,7 @@ mod tests {
       .with_fields(&mut fields)
       .build()
       .unwrap();
-    let schema_descr = SchemaDescriptor::new(Rc::new(schema));
+    let schema_descr = SchemaDescriptor::new(Arc::new(schema));
 
     let t_column_orders = Some(vec![
       TColumnOrder::TYPEORDER(TypeDefinedOrder::new()),
@@ -636,7 +1227,7 @@ mod tests {
   #[should_panic(expected = "Column order length mismatch")]
   fn test_file_reader_column_orders_len_mismatch() {
     let schema = SchemaType::group_type_builder("schema").build().unwrap();
-    let schema_descr = SchemaDescriptor::new(Rc::new(schema));
+    let schema_descr = SchemaDescriptor::new(Arc::new(schema));
 
     let t_column_orders = Some(vec![TColumnOrder::TYPEORDER(TypeDefinedOrder::new())]);
 
@@ -698,6 +1289,440 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_file_reader_column_statistics() {
+    let reader = SerializedFileReader::new(get_test_file("10k-v2.parquet")).unwrap();
+
+    // Compute actual min/max of "int32_field" by reading all values
+    let file_metadata = reader.metadata().file_metadata();
+    let field = file_metadata.schema().get_fields()[1].clone();
+    let projection = SchemaType::group_type_builder(file_metadata.schema().name())
+      .with_fields(&mut vec![field])
+      .build()
+      .unwrap();
+    let values = reader
+      .get_row_iter(Some(projection))
+      .unwrap()
+      .map(|row| row.get_int(0).unwrap())
+      .collect::<Vec<i32>>();
+    let min = *values.iter().min().unwrap();
+    let max = *values.iter().max().unwrap();
+
+    let stats = reader.column_statistics(1).unwrap().unwrap();
+    assert_eq!(stats.null_count(), 0);
+    match stats {
+      Statistics::Int32(ref typed) => {
+        assert_eq!(*typed.min(), min);
+        assert_eq!(*typed.max(), max);
+      },
+      _ => panic!("Expected INT32 statistics, found {}", stats),
+    }
+
+    // Out of bound column
+    assert_eq!(
+      reader.column_statistics(100).unwrap_err(),
+      general_err!("Column index 100 out of bound, number of columns: 8")
+    );
+
+    // Column chunks without statistics
+    let reader =
+      SerializedFileReader::new(get_test_file("alltypes_plain.parquet")).unwrap();
+    assert_eq!(reader.column_statistics(0).unwrap(), None);
+  }
+
+  #[test]
+  fn test_file_reader_metadata_outlives_reader() {
+    let metadata = {
+      let reader =
+        SerializedFileReader::new(get_test_file("alltypes_plain.parquet")).unwrap();
+      let metadata = reader.metadata();
+      assert_eq!(Arc::strong_count(&metadata), 2);
+      metadata
+    };
+    assert_eq!(Arc::strong_count(&metadata), 1);
+    assert_eq!(metadata.num_row_groups(), 1);
+    assert_eq!(metadata.file_metadata().num_rows(), 8);
+    assert_eq!(metadata.row_group(0).num_columns(), 11);
+  }
+
+  #[test]
+  fn test_file_reader_column_chunk_count_mismatch() {
+    let data = include_bytes!("../../data/alltypes_plain.parquet").to_vec();
+    let metadata_len = LittleEndian::read_i32(&data[data.len() - FOOTER_SIZE..]) as usize;
+    let metadata_start = data.len() - FOOTER_SIZE - metadata_len;
+    let t_file_metadata = {
+      let mut prot = TCompactInputProtocol::new(&data[metadata_start..]);
+      TFileMetaData::read_from_in_protocol(&mut prot).unwrap()
+    };
+
+    // Surplus and missing column chunks of the only row group
+    let mut surplus = t_file_metadata.clone();
+    let column = surplus.row_groups[0].columns[10].clone();
+    surplus.row_groups[0].columns.push(column);
+    let mut missing = t_file_metadata.clone();
+    missing.row_groups[0].columns.pop();
+
+    for &(ref t_file_metadata, num_columns) in &[(surplus, 12), (missing, 10)] {
+      let mut data = data[..metadata_start].to_vec();
+      t_file_metadata
+        .write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut data))
+        .unwrap();
+      let mut footer = [0; FOOTER_SIZE];
+      LittleEndian::write_i32(&mut footer, (data.len() - metadata_start) as i32);
+      footer[4..].copy_from_slice(&PARQUET_MAGIC);
+      data.extend_from_slice(&footer);
+
+      let file = get_temp_file("file-reader-column-chunk-count.parquet", &data);
+      assert_eq!(
+        SerializedFileReader::new(file).err().unwrap(),
+        general_err!(
+          "Invalid Parquet file. Row group 0 has {} column chunks, expected 11",
+          num_columns
+        )
+      );
+    }
+  }
+
+  #[test]
+  fn test_validate_layout() {
+    for name in &[
+      "alltypes_plain.parquet",
+      "alltypes_dictionary.parquet",
+      "10k-v2.parquet",
+      "nested_maps.snappy.parquet",
+      "nested_lists.snappy.parquet",
+      "test_datapage_v2.snappy.parquet",
+    ] {
+      let reader = SerializedFileReader::new(get_test_file(name)).unwrap();
+      assert_eq!(validate_layout(&reader).unwrap(), Vec::<String>::new());
+    }
+
+    // File metadata declares 0 rows, while the only row group has 6 rows
+    let reader =
+      SerializedFileReader::new(get_test_file("repeated_no_annotation.parquet")).unwrap();
+    assert_eq!(
+      validate_layout(&reader).unwrap(),
+      vec!["Sum of row group rows 6 does not match file num_rows 0".to_owned()]
+    );
+
+    // Trailing bytes after the footer are not mistaken for metadata length
+    let mut data = include_bytes!("../../data/alltypes_plain.parquet").to_vec();
+    data.extend_from_slice(&[0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0x0F]);
+    let file = get_temp_file("validate-layout-trailing-bytes.parquet", &data);
+    let options = ReadOptions::builder()
+      .set_tolerate_trailing_bytes(true)
+      .build();
+    let reader = SerializedFileReader::new_with_options(file, &options).unwrap();
+    assert_eq!(validate_layout(&reader).unwrap(), Vec::<String>::new());
+  }
+
+  #[test]
+  fn test_file_reader_num_values_num_rows() {
+    let reader =
+      SerializedFileReader::new(get_test_file("nested_lists.snappy.parquet")).unwrap();
+    let row_group_metadata = reader.metadata().row_group(0);
+    assert_eq!(row_group_metadata.num_rows(), 3);
+
+    // Leaf column of a nested list has more values than rows
+    let column = row_group_metadata.column(0);
+    assert_eq!(column.column_descr().max_rep_level(), 3);
+    assert_eq!(column.num_values(), 18);
+
+    // Non-repeated column has one value per row
+    let column = row_group_metadata.column(1);
+    assert_eq!(column.column_descr().max_rep_level(), 0);
+    assert_eq!(column.num_values(), 3);
+
+    // Repetition levels map leaf values to rows
+    let row_group_reader = reader.get_row_group(0).unwrap();
+    let num_rows = match row_group_reader.get_column_reader(0).unwrap() {
+      ColumnReader::ByteArrayColumnReader(mut typed) => {
+        let mut values = vec![ByteArray::new(); 32];
+        let mut def_levels = vec![0; 32];
+        let mut rep_levels = vec![0; 32];
+        let (_, levels_read) = typed
+          .read_batch(32, Some(&mut def_levels), Some(&mut rep_levels), &mut values)
+          .unwrap();
+        assert_eq!(levels_read, 18);
+        rep_levels[..levels_read].iter().filter(|&&level| level == 0).count()
+      },
+      _ => panic!("Expected BYTE_ARRAY column"),
+    };
+    assert_eq!(num_rows, 3);
+  }
+
+  #[test]
+  fn test_row_group_reader_lazy_column_chunks() {
+    let fields = ["a", "b", "c"]
+      .iter()
+      .map(|name| make_field(name, Type::INT32, Repetition::REQUIRED))
+      .collect();
+    let file = write_test_file(
+      "test_row_group_reader_lazy_column_chunks",
+      fields,
+      WriterProperties::builder().build(),
+      1,
+      |_, col_writer| write_int32_values(col_writer, &[1, 2, 3]),
+    );
+
+    let tracking_file = TrackingFile::new(file);
+    let reads = tracking_file.reads.clone();
+    let reader = SerializedFileReader::new(tracking_file.try_clone().unwrap()).unwrap();
+    let metadata = reader.metadata().row_group(0);
+    assert_eq!(metadata.num_columns(), 3);
+    let col = metadata.column(0);
+    let col_start = col.dictionary_page_offset().unwrap_or(col.data_page_offset()) as u64;
+    let col_end = col_start + col.compressed_size() as u64;
+
+    // Opening a row group does not read any column chunks
+    reads.borrow_mut().clear();
+    let row_group_reader = reader.get_row_group(0).unwrap();
+    assert!(reads.borrow().is_empty());
+
+    // Only the first column chunk is read
+    assert_eq!(read_int32_values(&*row_group_reader, 0), vec![1, 2, 3]);
+    assert!(!reads.borrow().is_empty());
+    for &(start, _) in reads.borrow().iter() {
+      assert!(start >= col_start && start < col_end);
+    }
+
+    // Column chunks are not cached by default, so the same column is read again
+    reads.borrow_mut().clear();
+    assert_eq!(read_int32_values(&*row_group_reader, 0), vec![1, 2, 3]);
+    assert!(!reads.borrow().is_empty());
+
+    // Cached column chunk is read from the file exactly once
+    let options = ReadOptions::builder().set_cache_column_chunks(true).build();
+    let reader =
+      SerializedFileReader::new_with_options(tracking_file, &options).unwrap();
+    let row_group_reader = reader.get_row_group(0).unwrap();
+    reads.borrow_mut().clear();
+    assert_eq!(read_int32_values(&*row_group_reader, 0), vec![1, 2, 3]);
+    let mut bytes_read = 0;
+    for &(start, end) in reads.borrow().iter() {
+      assert!(start >= col_start && end <= col_end);
+      bytes_read += end - start;
+    }
+    assert_eq!(bytes_read, col_end - col_start);
+
+    reads.borrow_mut().clear();
+    assert_eq!(read_int32_values(&*row_group_reader, 0), vec![1, 2, 3]);
+    assert!(reads.borrow().is_empty());
+  }
+
+  #[test]
+  fn test_file_reader_skip_unsupported_columns() {
+    let fields = vec![
+      make_field("a", Type::INT32, Repetition::REQUIRED),
+      make_field("b", Type::INT32, Repetition::REQUIRED),
+    ];
+    let file = write_test_file(
+      "test_file_reader_skip_unsupported_columns",
+      fields,
+      WriterProperties::builder()
+        .set_dictionary_enabled(false)
+        .build(),
+      1,
+      |_, col_writer| write_int32_values(col_writer, &[1, 2, 3]),
+    );
+
+    // Rewrite column chunk metadata of column "b" with encoding that cannot be decoded
+    let mut data = Vec::new();
+    let mut source = file.try_clone().unwrap();
+    source.seek(SeekFrom::Start(0)).unwrap();
+    source.read_to_end(&mut data).unwrap();
+    let metadata_len = LittleEndian::read_i32(&data[data.len() - FOOTER_SIZE..]) as usize;
+    let metadata_start = data.len() - FOOTER_SIZE - metadata_len;
+    let mut t_file_metadata = {
+      let mut prot = TCompactInputProtocol::new(&data[metadata_start..]);
+      TFileMetaData::read_from_in_protocol(&mut prot).unwrap()
+    };
+    t_file_metadata.row_groups[0].columns[1]
+      .meta_data
+      .as_mut()
+      .unwrap()
+      .encodings = vec![
+      parquet_format::Encoding::DELTA_BYTE_ARRAY,
+      parquet_format::Encoding::RLE,
+    ];
+    data.truncate(metadata_start);
+    t_file_metadata
+      .write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut data))
+      .unwrap();
+    let mut footer = [0; FOOTER_SIZE];
+    LittleEndian::write_i32(&mut footer, (data.len() - metadata_start) as i32);
+    footer[4..].copy_from_slice(&PARQUET_MAGIC);
+    data.extend_from_slice(&footer);
+    let file = get_temp_file("test_file_reader_skip_unsupported_columns_patched", &data);
+
+    let options = ReadOptions::builder()
+      .set_skip_unsupported_columns(true)
+      .build();
+    let reader = SerializedFileReader::new_with_options(file, &options).unwrap();
+    assert_eq!(reader.unsupported_columns(), vec![1]);
+
+    // Unsupported column is left out, other columns are read
+    let rows = reader.get_row_iter(None).unwrap().collect::<Vec<_>>();
+    assert_eq!(rows.len(), 3);
+    for (i, row) in rows.iter().enumerate() {
+      assert_eq!(row.len(), 1);
+      assert_eq!(row.get_int(0).unwrap(), i as i32 + 1);
+    }
+
+    // Column chunk support is decided from metadata only
+    let row_group_reader = reader.get_row_group(0).unwrap();
+    assert!(row_group_reader.is_column_supported(0));
+    assert!(!row_group_reader.is_column_supported(1));
+  }
+
+  #[test]
+  fn test_row_group_reader_read_dictionary() {
+    let fields = vec![
+      make_field("a", Type::INT32, Repetition::REQUIRED),
+      make_field("b", Type::BYTE_ARRAY, Repetition::OPTIONAL),
+      make_field("c", Type::INT32, Repetition::REQUIRED),
+    ];
+    let file = write_test_file(
+      "test_row_group_reader_read_dictionary",
+      fields,
+      WriterProperties::builder()
+        .set_column_dictionary_enabled(ColumnPath::from("c"), false)
+        .build(),
+      1,
+      |_, col_writer| match *col_writer {
+        ColumnWriter::ByteArrayColumnWriter(ref mut typed) => {
+          let values: Vec<ByteArray> = vec!["x".into(), "y".into(), "x".into()];
+          typed
+            .write_batch(&values, Some(&[1, 0, 1, 1, 0, 0]), None)
+            .unwrap();
+        },
+        ref mut col_writer => write_int32_values(col_writer, &[3, 1, 3, 2, 1, 1]),
+      },
+    );
+
+    let tracking_file = TrackingFile::new(file);
+    let reads = tracking_file.reads.clone();
+    let reader = SerializedFileReader::new(tracking_file).unwrap();
+    let row_group_reader = reader.get_row_group(0).unwrap();
+    assert_eq!(
+      row_group_reader.read_dictionary::<Int32Type>(0).unwrap(),
+      Some(vec![3, 1, 2])
+    );
+    assert_eq!(
+      row_group_reader.read_dictionary::<ByteArrayType>(1).unwrap(),
+      Some(vec![ByteArray::from("x"), ByteArray::from("y")])
+    );
+
+    // Column chunk without dictionary page is recognized from metadata, without IO
+    reads.borrow_mut().clear();
+    assert_eq!(row_group_reader.read_dictionary::<Int32Type>(2).unwrap(), None);
+    assert!(reads.borrow().is_empty());
+
+    assert_eq!(
+      row_group_reader.read_dictionary::<Int64Type>(0).unwrap_err(),
+      general_err!("Column 0 has physical type INT32, but INT64 was requested")
+    );
+  }
+
+  #[test]
+  fn test_row_group_reader_read_dictionary_num_values() {
+    let file = write_test_file(
+      "test_row_group_reader_read_dictionary_num_values",
+      vec![make_field("a", Type::INT32, Repetition::REQUIRED)],
+      WriterProperties::builder().build(),
+      1,
+      |_, col_writer| write_int32_values(col_writer, &[3, 1, 3, 2, 1, 1]),
+    );
+
+    // Rewrite dictionary page header with number of values that does not fit the page
+    let mut data = Vec::new();
+    let mut source = file.try_clone().unwrap();
+    source.seek(SeekFrom::Start(0)).unwrap();
+    source.read_to_end(&mut data).unwrap();
+    let metadata = SerializedFileReader::new(file).unwrap().metadata();
+    let header_start =
+      metadata.row_group(0).column(0).dictionary_page_offset().unwrap() as usize;
+    let (mut page_header, header_len) = {
+      let mut header_buf = &data[header_start..];
+      let page_header = {
+        let mut prot = TCompactInputProtocol::new(&mut header_buf);
+        PageHeader::read_from_in_protocol(&mut prot).unwrap()
+      };
+      (page_header, data.len() - header_start - header_buf.len())
+    };
+    page_header.dictionary_page_header.as_mut().unwrap().num_values = 1 << 30;
+    let mut header_buf = Vec::new();
+    page_header
+      .write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut header_buf))
+      .unwrap();
+    data.splice(header_start..header_start + header_len, header_buf);
+    let file = get_temp_file("test_row_group_reader_read_dictionary_num_values", &data);
+
+    let reader = SerializedFileReader::new(file).unwrap();
+    let row_group_reader = reader.get_row_group(0).unwrap();
+    assert_eq!(
+      row_group_reader.read_dictionary::<Int32Type>(0).unwrap_err(),
+      general_err!(
+        "Dictionary page has 1073741824 values, but 12 bytes can hold at most 3 values"
+      )
+    );
+  }
+
+  #[test]
+  fn test_file_reader_read_rows_limit() {
+    // 10000 rows in 2 row groups, with 100 rows per data page
+    let file = write_test_file(
+      "test_file_reader_read_rows_limit",
+      vec![make_field("col", Type::INT64, Repetition::REQUIRED)],
+      WriterProperties::builder()
+        .set_dictionary_enabled(false)
+        .set_data_page_row_count_limit(100)
+        .build(),
+      2,
+      |row_group, col_writer| {
+        let start = row_group as i64 * 5000;
+        let values = (start..start + 5000).collect::<Vec<i64>>();
+        match *col_writer {
+          ColumnWriter::Int64ColumnWriter(ref mut typed) => {
+            typed.write_batch(&values, None, None).unwrap();
+          },
+          _ => panic!("Expected INT64 column writer"),
+        }
+      },
+    );
+
+    let tracking_file = TrackingFile::new(file);
+    let reads = tracking_file.reads.clone();
+    let reader = SerializedFileReader::new(tracking_file).unwrap();
+    reads.borrow_mut().clear();
+
+    let rows = reader.read_rows_limit(None, 5).unwrap();
+    assert_eq!(
+      rows.iter().map(|row| row.get_long(0).unwrap()).collect::<Vec<_>>(),
+      vec![0, 1, 2, 3, 4]
+    );
+
+    // Only pages at the beginning of the first row group are read
+    let row_group_metadata = reader.metadata().row_group(0);
+    let chunk = row_group_metadata.column(0);
+    let chunk_start = chunk.data_page_offset() as u64;
+    let chunk_end = chunk_start + chunk.compressed_size() as u64;
+    assert!(!reads.borrow().is_empty());
+    for &(start, end) in reads.borrow().iter() {
+      assert!(start >= chunk_start && end <= chunk_start + (chunk_end - chunk_start) / 2);
+    }
+
+    assert_eq!(reader.read_rows_limit(None, 0).unwrap(), vec![]);
+    assert_eq!(reader.read_rows_limit(None, 20000).unwrap().len(), 10000);
+
+    // Limit applies to records, not to values of nested columns
+    let reader =
+      SerializedFileReader::new(get_test_file("nested_lists.snappy.parquet")).unwrap();
+    let all_rows = reader.get_row_iter(None).unwrap().collect::<Vec<_>>();
+    let rows = reader.read_rows_limit(None, 2).unwrap();
+    assert_eq!(rows, &all_rows[..2]);
+  }
+
   #[test]
   fn test_file_reader() {
     let test_file = get_test_file("alltypes_plain.parquet");
@@ -878,4 +1903,117 @@ mod tests {
     }
     assert_eq!(page_count, 2);
   }
+
+  // Reader that records byte range `(start, end)` of every read from the underlying
+  // file, ranges are shared by all clones of the handle.
+  struct TrackingFile {
+    file: File,
+    reads: Rc<RefCell<Vec<(u64, u64)>>>,
+  }
+
+  impl TrackingFile {
+    fn new(file: File) -> Self {
+      Self {
+        file,
+        reads: Rc::new(RefCell::new(Vec::new())),
+      }
+    }
+  }
+
+  impl Read for TrackingFile {
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+      let start = self.file.seek(SeekFrom::Current(0))?;
+      let num_bytes = self.file.read(buf)?;
+      self.reads.borrow_mut().push((start, start + num_bytes as u64));
+      Ok(num_bytes)
+    }
+  }
+
+  impl Seek for TrackingFile {
+    fn seek(&mut self, pos: SeekFrom) -> ::std::io::Result<u64> { self.file.seek(pos) }
+  }
+
+  impl Length for TrackingFile {
+    fn len(&self) -> u64 { self.file.len() }
+  }
+
+  impl TryClone for TrackingFile {
+    fn try_clone(&self) -> Result<Self> {
+      Ok(Self {
+        file: TryClone::try_clone(&self.file)?,
+        reads: self.reads.clone(),
+      })
+    }
+  }
+
+  /// Returns primitive field of a test schema.
+  fn make_field(name: &str, physical_type: Type, repetition: Repetition) -> TypePtr {
+    Arc::new(
+      SchemaType::primitive_type_builder(name, physical_type)
+        .with_repetition(repetition)
+        .build()
+        .unwrap(),
+    )
+  }
+
+  /// Writes temp file `name` with `fields` as schema and `num_row_groups` row groups.
+  /// Every column chunk is written by `write_column`, which is called with index of the
+  /// row group and column writer.
+  fn write_test_file<F>(
+    name: &str,
+    mut fields: Vec<TypePtr>,
+    props: WriterProperties,
+    num_row_groups: usize,
+    mut write_column: F,
+  ) -> File
+  where
+    F: FnMut(usize, &mut ColumnWriter),
+  {
+    let schema = Arc::new(
+      SchemaType::group_type_builder("schema")
+        .with_fields(&mut fields)
+        .build()
+        .unwrap(),
+    );
+    let file = get_temp_file(name, &[]);
+    let mut file_writer =
+      SerializedFileWriter::new(file.try_clone().unwrap(), schema, Rc::new(props))
+        .unwrap();
+    for i in 0..num_row_groups {
+      let mut row_group_writer = file_writer.next_row_group().unwrap();
+      while let Some(mut col_writer) = row_group_writer.next_column().unwrap() {
+        write_column(i, &mut col_writer);
+        row_group_writer.close_column(col_writer).unwrap();
+      }
+      file_writer.close_row_group(row_group_writer).unwrap();
+    }
+    file_writer.close().unwrap();
+    file
+  }
+
+  /// Writes `values` with column writer of INT32 column.
+  fn write_int32_values(col_writer: &mut ColumnWriter, values: &[i32]) {
+    match *col_writer {
+      ColumnWriter::Int32ColumnWriter(ref mut typed) => {
+        typed.write_batch(values, None, None).unwrap();
+      },
+      _ => panic!("Expected INT32 column writer"),
+    }
+  }
+
+  /// Reads all values of the `i`th column chunk, which must be INT32 column.
+  fn read_int32_values(row_group_reader: &RowGroupReader, i: usize) -> Vec<i32> {
+    match row_group_reader.get_column_reader(i).unwrap() {
+      ColumnReader::Int32ColumnReader(mut typed) => {
+        let num_values = row_group_reader.metadata().column(i).num_values() as usize;
+        let mut values = vec![0; num_values];
+        let (values_read, _) = typed
+          .read_batch(num_values, None, None, &mut values)
+          .unwrap();
+        assert_eq!(values_read, num_values);
+        values
+      },
+      _ => panic!("Expected INT32 column reader"),
+    }
+  }
 }
@@ -39,7 +39,7 @@
 
 use std::{cmp, fmt};
 
-use basic::Type;
+use basic::{SortOrder, Type};
 use byteorder::{ByteOrder, LittleEndian};
 use data_type::*;
 use parquet_format::Statistics as TStatistics;
@@ -144,9 +144,9 @@ pub fn from_thrift(
           old_format,
         ),
         Type::INT96 => {
-          // INT96 statistics may not be correct, because comparison is signed
-          // byte-wise, not actual timestamps. It is recommended to ignore min/max
-          // statistics for INT96 columns.
+          // INT96 statistics may not be correct, because some writers compare values
+          // byte-wise, not as actual timestamps. Values are decoded into `Int96`,
+          // which is ordered as timestamp, see `Statistics::merge`.
           let min = min.map(|data| {
             assert_eq!(data.len(), 12);
             unsafe {
@@ -242,7 +242,7 @@ pub fn to_thrift(stats: Option<&Statistics>) -> Option<TStatistics> {
 }
 
 /// Statistics for a column chunk and data page.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Statistics {
   Boolean(TypedStatistics<BoolType>),
   Int32(TypedStatistics<Int32Type>),
@@ -306,6 +306,62 @@ impl Statistics {
   /// Panics if max value is not set.
   pub fn max_bytes(&self) -> &[u8] { statistics_enum_func![self, max_bytes] }
 
+  /// Merges these statistics with statistics of another chunk of the same column.
+  ///
+  /// Resulting min is the smallest of both min values, max is the largest of both max
+  /// values, and null counts are added up. Distinct counts cannot be combined and are
+  /// dropped. Values are compared according to `sort_order` of the column, see
+  /// [`ColumnOrder::get_sort_order`](`::basic::ColumnOrder::get_sort_order`).
+  /// INT96 values are always compared as timestamps, even though their sort order is
+  /// undefined.
+  ///
+  /// Returns `None` if statistics have different physical types, if only one of them
+  /// has deprecated min/max, or if sort order is undefined for a non-INT96 column.
+  pub fn merge(&self, other: &Statistics, sort_order: SortOrder) -> Option<Statistics> {
+    let undefined =
+      sort_order == SortOrder::UNDEFINED && self.physical_type() != Type::INT96;
+    if undefined || self.is_min_max_deprecated() != other.is_min_max_deprecated() {
+      return None;
+    }
+    let unsigned = sort_order == SortOrder::UNSIGNED;
+    let res = match (self, other) {
+      (Statistics::Boolean(a), Statistics::Boolean(b)) => {
+        Statistics::Boolean(a.merge(b, |x, y| x < y))
+      },
+      (Statistics::Int32(a), Statistics::Int32(b)) if unsigned => {
+        Statistics::Int32(a.merge(b, |x, y| (*x as u32) < (*y as u32)))
+      },
+      (Statistics::Int32(a), Statistics::Int32(b)) => {
+        Statistics::Int32(a.merge(b, |x, y| x < y))
+      },
+      (Statistics::Int64(a), Statistics::Int64(b)) if unsigned => {
+        Statistics::Int64(a.merge(b, |x, y| (*x as u64) < (*y as u64)))
+      },
+      (Statistics::Int64(a), Statistics::Int64(b)) => {
+        Statistics::Int64(a.merge(b, |x, y| x < y))
+      },
+      (Statistics::Int96(a), Statistics::Int96(b)) => {
+        Statistics::Int96(a.merge(b, |x, y| x < y))
+      },
+      (Statistics::Float(a), Statistics::Float(b)) => {
+        Statistics::Float(a.merge(b, |x, y| x < y))
+      },
+      (Statistics::Double(a), Statistics::Double(b)) => {
+        Statistics::Double(a.merge(b, |x, y| x < y))
+      },
+      (Statistics::ByteArray(a), Statistics::ByteArray(b)) => Statistics::ByteArray(
+        a.merge(b, |x, y| bytes_less_than(x.data(), y.data(), unsigned)),
+      ),
+      (Statistics::FixedLenByteArray(a), Statistics::FixedLenByteArray(b)) => {
+        Statistics::FixedLenByteArray(
+          a.merge(b, |x, y| bytes_less_than(x.data(), y.data(), unsigned)),
+        )
+      },
+      _ => return None,
+    };
+    Some(res)
+  }
+
   /// Returns physical type associated with statistics.
   pub fn physical_type(&self) -> Type {
     match self {
@@ -321,6 +377,34 @@ impl Statistics {
   }
 }
 
+/// Compares byte arrays either byte-wise as unsigned bytes, or as signed big-endian
+/// two's complement integers (e.g. decimals), which may have different lengths.
+fn bytes_less_than(a: &[u8], b: &[u8], unsigned: bool) -> bool {
+  if unsigned {
+    return a < b;
+  }
+  let sign_byte = |v: &[u8]| match v.first() {
+    Some(byte) if *byte >= 0x80 => 0xFF,
+    _ => 0,
+  };
+  let (sign_a, sign_b) = (sign_byte(a), sign_byte(b));
+  if sign_a != sign_b {
+    return sign_a > sign_b;
+  }
+  // Same sign, compare values sign-extended to the same length
+  let len = cmp::max(a.len(), b.len());
+  let byte_at = |v: &[u8], sign: u8, i: usize| {
+    if i < len - v.len() { sign } else { v[i - (len - v.len())] }
+  };
+  for i in 0..len {
+    let (x, y) = (byte_at(a, sign_a, i), byte_at(b, sign_b, i));
+    if x != y {
+      return x < y;
+    }
+  }
+  false
+}
+
 impl fmt::Display for Statistics {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
@@ -401,6 +485,39 @@ impl<T: DataType> TypedStatistics<T> {
 
   /// Returns `true` if statistics were created using old min/max fields.
   fn is_min_max_deprecated(&self) -> bool { self.is_min_max_deprecated }
+
+  /// Merges two typed statistics, `less_than` defines the order of values.
+  /// If min or max is not set on either side, value from the other side is used.
+  fn merge<F>(&self, other: &TypedStatistics<T>, less_than: F) -> Self
+  where F: Fn(&T::T, &T::T) -> bool {
+    let min = match (&self.min, &other.min) {
+      (Some(a), Some(b)) => Some(if less_than(b, a) { b } else { a }.clone()),
+      (a, b) => a.as_ref().or(b.as_ref()).cloned(),
+    };
+    let max = match (&self.max, &other.max) {
+      (Some(a), Some(b)) => Some(if less_than(a, b) { b } else { a }.clone()),
+      (a, b) => a.as_ref().or(b.as_ref()).cloned(),
+    };
+    Self {
+      min,
+      max,
+      distinct_count: None,
+      null_count: self.null_count + other.null_count,
+      is_min_max_deprecated: self.is_min_max_deprecated || other.is_min_max_deprecated,
+    }
+  }
+}
+
+impl<T: DataType> Clone for TypedStatistics<T> {
+  fn clone(&self) -> Self {
+    Self {
+      min: self.min.clone(),
+      max: self.max.clone(),
+      distinct_count: self.distinct_count,
+      null_count: self.null_count,
+      is_min_max_deprecated: self.is_min_max_deprecated,
+    }
+  }
 }
 
 impl<T: DataType> fmt::Display for TypedStatistics<T> {
@@ -622,6 +739,196 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_statistics_merge() {
+    let stats = Statistics::int32(Some(3), Some(10), Some(4), 1, false)
+      .merge(
+        &Statistics::int32(Some(-5), Some(7), Some(2), 2, false),
+        SortOrder::SIGNED,
+      )
+      .unwrap();
+    assert_eq!(stats, Statistics::int32(Some(-5), Some(10), None, 3, false));
+
+    // Chunk with only nulls does not have min/max set
+    let stats = Statistics::double(None, None, None, 4, true)
+      .merge(
+        &Statistics::double(Some(1.5), Some(2.5), None, 0, true),
+        SortOrder::SIGNED,
+      )
+      .unwrap();
+    assert_eq!(stats, Statistics::double(Some(1.5), Some(2.5), None, 4, true));
+
+    let stats = Statistics::byte_array(
+      Some(ByteArray::from(vec![1, 2])),
+      Some(ByteArray::from(vec![200])),
+      None,
+      0,
+      false,
+    )
+    .merge(
+      &Statistics::byte_array(
+        Some(ByteArray::from(vec![1])),
+        Some(ByteArray::from(vec![1, 255])),
+        None,
+        0,
+        false,
+      ),
+      SortOrder::UNSIGNED,
+    )
+    .unwrap();
+    assert_eq!(
+      stats,
+      Statistics::byte_array(
+        Some(ByteArray::from(vec![1])),
+        Some(ByteArray::from(vec![200])),
+        None,
+        0,
+        false
+      )
+    );
+
+    assert!(Statistics::int32(Some(1), Some(2), None, 0, false)
+      .merge(
+        &Statistics::int64(Some(1), Some(2), None, 0, false),
+        SortOrder::SIGNED
+      )
+      .is_none());
+  }
+
+  #[test]
+  fn test_statistics_merge_sort_order() {
+    // Unsigned values stored as INT32, -1 is 0xFFFFFFFF
+    let stats = Statistics::int32(Some(1), Some(-1), None, 0, false)
+      .merge(
+        &Statistics::int32(Some(0), Some(5), None, 0, false),
+        SortOrder::UNSIGNED,
+      )
+      .unwrap();
+    assert_eq!(stats, Statistics::int32(Some(0), Some(-1), None, 0, false));
+
+    let stats = Statistics::int64(Some(-2), Some(-1), None, 0, false)
+      .merge(
+        &Statistics::int64(Some(3), Some(5), None, 0, false),
+        SortOrder::UNSIGNED,
+      )
+      .unwrap();
+    assert_eq!(stats, Statistics::int64(Some(3), Some(-1), None, 0, false));
+
+    // Signed byte arrays, e.g. decimals, of different length: -256, -1, 1, 256
+    let stats = Statistics::byte_array(
+      Some(ByteArray::from(vec![0xFF])),
+      Some(ByteArray::from(vec![0x01, 0x00])),
+      None,
+      0,
+      false,
+    )
+    .merge(
+      &Statistics::byte_array(
+        Some(ByteArray::from(vec![0xFF, 0x00])),
+        Some(ByteArray::from(vec![0x01])),
+        None,
+        0,
+        false,
+      ),
+      SortOrder::SIGNED,
+    )
+    .unwrap();
+    assert_eq!(
+      stats,
+      Statistics::byte_array(
+        Some(ByteArray::from(vec![0xFF, 0x00])),
+        Some(ByteArray::from(vec![0x01, 0x00])),
+        None,
+        0,
+        false
+      )
+    );
+
+    // Undefined sort order
+    assert!(Statistics::int32(Some(1), Some(2), None, 0, false)
+      .merge(
+        &Statistics::int32(Some(1), Some(2), None, 0, false),
+        SortOrder::UNDEFINED
+      )
+      .is_none());
+
+    // Deprecated and non-deprecated min/max
+    assert!(Statistics::int32(Some(1), Some(2), None, 0, true)
+      .merge(
+        &Statistics::int32(Some(1), Some(2), None, 0, false),
+        SortOrder::SIGNED
+      )
+      .is_none());
+  }
+
+  #[test]
+  fn test_statistics_int96_timestamp_order() {
+    // Byte-wise `earlier` is greater, because its first byte is larger
+    let earlier = Int96::from(vec![1, 0, 2457999]);
+    let later = Int96::from(vec![0, 0, 2458000]);
+
+    let first =
+      Statistics::int96(Some(later.clone()), Some(later.clone()), None, 0, false);
+    let second =
+      Statistics::int96(Some(earlier.clone()), Some(earlier.clone()), None, 0, false);
+    let stats = first.merge(&second, SortOrder::UNDEFINED).unwrap();
+    assert_eq!(
+      stats,
+      Statistics::int96(Some(earlier.clone()), Some(later.clone()), None, 0, false)
+    );
+  }
+
+  #[test]
+  fn test_statistics_int96_from_thrift() {
+    // Encodes INT96 value as 12 little-endian bytes, the way it is stored in files
+    fn int96_bytes(value: &[u32]) -> Vec<u8> {
+      let mut bytes = vec![0; 12];
+      for (i, v) in value.iter().enumerate() {
+        LittleEndian::write_u32(&mut bytes[i * 4..], *v);
+      }
+      bytes
+    }
+
+    // 2017-10-11 23:59:59.999 and 2017-10-12 00:00:00.001
+    let earlier = int96_bytes(&[0x913F_BDC0, 0x4E94, 2458038]);
+    let later = int96_bytes(&[0x000F_4240, 0, 2458039]);
+    assert!(earlier > later);
+
+    let thrift_stats = TStatistics {
+      max: None,
+      min: None,
+      null_count: Some(3),
+      distinct_count: None,
+      max_value: Some(later.clone()),
+      min_value: Some(earlier.clone()),
+    };
+    let stats = from_thrift(Type::INT96, Some(thrift_stats)).unwrap();
+    assert!(stats.has_min_max_set());
+    match stats {
+      Statistics::Int96(ref typed) => {
+        assert_eq!(typed.min().julian_day(), 2458038);
+        assert_eq!(typed.max().julian_day(), 2458039);
+        assert!(typed.min() < typed.max());
+      },
+      _ => panic!("Expected INT96 statistics, found {:?}", stats),
+    }
+    assert_eq!(stats.min_bytes(), &earlier[..]);
+    assert_eq!(stats.max_bytes(), &later[..]);
+
+    // Statistics of another chunk are merged by timestamp
+    let other = Statistics::int96(
+      Some(Int96::from(vec![0, 0, 2458037])),
+      Some(Int96::from(vec![0, 0, 2458038])),
+      None,
+      1,
+      false,
+    );
+    let merged = stats.merge(&other, SortOrder::UNDEFINED).unwrap();
+    assert_eq!(merged.null_count(), 4);
+    assert_eq!(merged.min_bytes(), &int96_bytes(&[0, 0, 2458037])[..]);
+    assert_eq!(merged.max_bytes(), &later[..]);
+  }
+
   #[test]
   fn test_statistics_from_thrift() {
     // Helper method to check statistics conversion.
@@ -19,9 +19,8 @@
 //! using row group writers and column writers respectively.
 
 use std::{
-  fs::File,
   io::{Seek, SeekFrom, Write},
-  rc::Rc,
+  sync::Arc,
 };
 
 use basic::PageType;
@@ -32,17 +31,32 @@ use column::{
 };
 use errors::{ParquetError, Result};
 use file::{
-  metadata::*, properties::WriterPropertiesPtr,
-  statistics::to_thrift as statistics_to_thrift, FOOTER_SIZE, PARQUET_MAGIC,
+  metadata::*,
+  properties::WriterPropertiesPtr,
+  reader::{FileReader, ParquetReader, SerializedFileReader, TryClone},
+  statistics::to_thrift as statistics_to_thrift,
+  FOOTER_SIZE, PARQUET_MAGIC,
 };
 use parquet_format as parquet;
 use schema::types::{self, SchemaDescPtr, SchemaDescriptor, TypePtr};
-use thrift::protocol::{TCompactOutputProtocol, TOutputProtocol};
-use util::io::{FileSink, Position};
+use thrift::protocol::{TCompactInputProtocol, TCompactOutputProtocol, TOutputProtocol};
+use util::{
+  io::{FileSink, Position},
+  memory::ByteBufferPtr,
+};
 
 // ----------------------------------------------------------------------
 // APIs for file & row group writers
 
+/// ParquetWriter is the interface which needs to be fulfilled to be able to write
+/// a Parquet file into a sink.
+///
+/// Clones obtained through `TryClone` must share the write position with the original,
+/// the same way `File::try_clone` does, because column chunks are written through
+/// separate handles.
+pub trait ParquetWriter: Write + Seek + TryClone {}
+impl<T: Write + Seek + TryClone> ParquetWriter for T {}
+
 /// Parquet file writer API.
 /// Provides methods to write row groups sequentially.
 ///
@@ -116,41 +130,59 @@ pub trait RowGroupWriter {
 
 /// A serialized implementation for Parquet [`FileWriter`].
 /// See documentation on file writer for more information.
-pub struct SerializedFileWriter {
-  file: File,
+///
+/// Data is streamed into the sink as it is produced: pages of a column chunk reach the
+/// sink when the column writer is closed, so at most one row group is buffered at any
+/// time. Only the file metadata is written at the end, when the writer is closed.
+pub struct SerializedFileWriter<W: ParquetWriter> {
+  file: W,
   schema: TypePtr,
   descr: SchemaDescPtr,
   props: WriterPropertiesPtr,
   total_num_rows: u64,
   row_groups: Vec<RowGroupMetaDataPtr>,
+  // Length of the original file when appending, 0 otherwise
+  append_file_len: u64,
+  // Row groups of the original file when appending, they are written as is
+  append_row_groups: Vec<parquet::RowGroup>,
+  // Footer fields that are written as is, taken from the original file when appending
+  key_value_metadata: Option<Vec<parquet::KeyValue>>,
+  created_by: Option<String>,
+  column_orders: Option<Vec<parquet::ColumnOrder>>,
   previous_writer_closed: bool,
   is_closed: bool,
 }
 
-impl SerializedFileWriter {
+impl<W: ParquetWriter> SerializedFileWriter<W> {
   /// Creates new file writer.
   pub fn new(
-    mut file: File,
+    mut file: W,
     schema: TypePtr,
     properties: WriterPropertiesPtr,
   ) -> Result<Self>
   {
     Self::start_file(&mut file)?;
+    let created_by = Some(properties.created_by().to_owned());
     Ok(Self {
       file,
       schema: schema.clone(),
-      descr: Rc::new(SchemaDescriptor::new(schema)),
+      descr: Arc::new(SchemaDescriptor::new(schema)),
       props: properties,
       total_num_rows: 0,
       row_groups: Vec::new(),
+      append_file_len: 0,
+      append_row_groups: Vec::new(),
+      key_value_metadata: None,
+      created_by,
+      column_orders: None,
       previous_writer_closed: true,
       is_closed: false,
     })
   }
 
   /// Writes magic bytes at the beginning of the file.
-  fn start_file(file: &mut File) -> Result<()> {
-    file.write(&PARQUET_MAGIC)?;
+  fn start_file(file: &mut W) -> Result<()> {
+    file.write_all(&PARQUET_MAGIC)?;
     Ok(())
   }
 
@@ -161,6 +193,7 @@ impl SerializedFileWriter {
   ) -> Result<()>
   {
     let row_group_metadata = row_group_writer.close()?;
+    self.total_num_rows += row_group_metadata.num_rows() as u64;
     self.row_groups.push(row_group_metadata);
     Ok(())
   }
@@ -172,31 +205,44 @@ impl SerializedFileWriter {
       schema: types::to_thrift(self.schema.as_ref())?,
       num_rows: self.total_num_rows as i64,
       row_groups: self
-        .row_groups
-        .as_slice()
-        .into_iter()
-        .map(|v| v.to_thrift())
+        .append_row_groups
+        .iter()
+        .cloned()
+        .chain(self.row_groups.iter().map(|v| v.to_thrift()))
         .collect(),
-      key_value_metadata: None,
-      created_by: Some(self.props.created_by().to_owned()),
-      column_orders: None,
+      key_value_metadata: self.key_value_metadata.clone(),
+      created_by: self.created_by.clone(),
+      column_orders: self.column_orders.clone(),
     };
 
-    // Write file metadata
-    let start_pos = self.file.seek(SeekFrom::Current(0))?;
+    let mut metadata_buffer = Vec::new();
     {
-      let mut protocol = TCompactOutputProtocol::new(&mut self.file);
+      let mut protocol = TCompactOutputProtocol::new(&mut metadata_buffer);
       file_metadata.write_to_out_protocol(&mut protocol)?;
       protocol.flush()?;
     }
-    let end_pos = self.file.seek(SeekFrom::Current(0))?;
+
+    // When appending, footer must not end before the end of the original file,
+    // otherwise old bytes would remain at the end. Sink cannot be truncated, so the gap
+    // is filled with zeros before file metadata.
+    let pos = self.file.seek(SeekFrom::Current(0))?;
+    let end_pos = pos + (metadata_buffer.len() + FOOTER_SIZE) as u64;
+    if end_pos < self.append_file_len {
+      self
+        .file
+        .write_all(&vec![0; (self.append_file_len - end_pos) as usize])?;
+    }
+
+    // Write file metadata
+    self.file.write_all(&metadata_buffer)?;
 
     // Write footer
     let mut footer_buffer: [u8; FOOTER_SIZE] = [0; FOOTER_SIZE];
-    let metadata_len = (end_pos - start_pos) as i32;
+    let metadata_len = metadata_buffer.len() as i32;
     LittleEndian::write_i32(&mut footer_buffer, metadata_len);
     (&mut footer_buffer[4..]).write(&PARQUET_MAGIC)?;
-    self.file.write(&footer_buffer)?;
+    self.file.write_all(&footer_buffer)?;
+    self.file.flush()?;
     Ok(())
   }
 
@@ -219,7 +265,62 @@ impl SerializedFileWriter {
   }
 }
 
-impl FileWriter for SerializedFileWriter {
+impl<W: 'static + ParquetWriter + ParquetReader> SerializedFileWriter<W> {
+  /// Opens existing Parquet file for appending row groups.
+  ///
+  /// File metadata is read from the file and writing continues from the position where
+  /// the old file metadata starts, so new row groups overwrite the old footer.
+  /// Row groups that are already in the file are preserved, and file metadata that
+  /// combines old and new row groups is written when the writer is closed. Metadata of
+  /// the old row groups is copied from the original file metadata unchanged.
+  ///
+  /// New row groups use schema of the file, `properties` are applied to new row groups
+  /// only. Key-value metadata, `created_by` and column orders of the original file are
+  /// kept in the new file metadata.
+  ///
+  /// File is never truncated. If fewer bytes are written than the old file metadata
+  /// occupied, for example, when no row groups are appended and new file metadata is
+  /// shorter, the gap before new file metadata is filled with zeros. These bytes are
+  /// not referenced by any row group and remain in the file.
+  pub fn open_for_append(mut file: W, properties: WriterPropertiesPtr) -> Result<Self> {
+    let metadata = SerializedFileReader::new(file.try_clone()?)?.metadata();
+
+    // File metadata has already been validated by the reader
+    let file_len = file.len();
+    let mut footer_buffer: [u8; FOOTER_SIZE] = [0; FOOTER_SIZE];
+    file.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))?;
+    file.read_exact(&mut footer_buffer)?;
+    let metadata_len = LittleEndian::read_i32(&footer_buffer[0..4]) as u64;
+    let metadata_start = file_len - FOOTER_SIZE as u64 - metadata_len;
+    file.seek(SeekFrom::Start(metadata_start))?;
+    let mut metadata_buffer = vec![0; metadata_len as usize];
+    file.read_exact(&mut metadata_buffer)?;
+    let mut prot = TCompactInputProtocol::new(metadata_buffer.as_slice());
+    let t_file_metadata = parquet::FileMetaData::read_from_in_protocol(&mut prot)
+      .map_err(|e| ParquetError::General(format!("Could not parse metadata: {}", e)))?;
+    file.seek(SeekFrom::Start(metadata_start))?;
+
+    let descr = metadata.file_metadata().schema_descr_ptr();
+    let append_row_groups = t_file_metadata.row_groups;
+    Ok(Self {
+      file,
+      schema: descr.root_schema_ptr(),
+      descr,
+      props: properties,
+      total_num_rows: append_row_groups.iter().map(|rg| rg.num_rows as u64).sum(),
+      row_groups: Vec::new(),
+      append_file_len: file_len,
+      append_row_groups,
+      key_value_metadata: t_file_metadata.key_value_metadata,
+      created_by: t_file_metadata.created_by,
+      column_orders: t_file_metadata.column_orders,
+      previous_writer_closed: true,
+      is_closed: false,
+    })
+  }
+}
+
+impl<W: 'static + ParquetWriter> FileWriter for SerializedFileWriter<W> {
   #[inline]
   fn next_row_group(&mut self) -> Result<Box<RowGroupWriter>> {
     self.assert_closed()?;
@@ -251,10 +352,10 @@ impl FileWriter for SerializedFileWriter {
 /// A serialized implementation for Parquet [`RowGroupWriter`].
 /// Coordinates writing of a row group with column writers.
 /// See documentation on row group writer for more information.
-pub struct SerializedRowGroupWriter {
+pub struct SerializedRowGroupWriter<W: ParquetWriter> {
   descr: SchemaDescPtr,
   props: WriterPropertiesPtr,
-  file: File,
+  file: W,
   total_rows_written: Option<u64>,
   total_bytes_written: u64,
   column_index: usize,
@@ -263,11 +364,11 @@ pub struct SerializedRowGroupWriter {
   column_chunks: Vec<ColumnChunkMetaDataPtr>,
 }
 
-impl SerializedRowGroupWriter {
+impl<W: ParquetWriter> SerializedRowGroupWriter<W> {
   pub fn new(
     schema_descr: SchemaDescPtr,
     properties: WriterPropertiesPtr,
-    file: &File,
+    file: &W,
   ) -> Self
   {
     let num_columns = schema_descr.num_columns();
@@ -299,7 +400,7 @@ impl SerializedRowGroupWriter {
 
     // Update row group writer metrics
     self.total_bytes_written += bytes_written;
-    self.column_chunks.push(Rc::new(metadata));
+    self.column_chunks.push(Arc::new(metadata));
     if let Some(rows) = self.total_rows_written {
       if rows != rows_written {
         return Err(general_err!(
@@ -334,7 +435,7 @@ impl SerializedRowGroupWriter {
   }
 }
 
-impl RowGroupWriter for SerializedRowGroupWriter {
+impl<W: 'static + ParquetWriter> RowGroupWriter for SerializedRowGroupWriter<W> {
   #[inline]
   fn next_column(&mut self) -> Result<Option<ColumnWriter>> {
     self.assert_closed()?;
@@ -374,7 +475,7 @@ impl RowGroupWriter for SerializedRowGroupWriter {
         .set_num_rows(self.total_rows_written.unwrap_or(0) as i64)
         .build()?;
 
-      self.row_group_metadata = Some(Rc::new(row_group_metadata));
+      self.row_group_metadata = Some(Arc::new(row_group_metadata));
     }
 
     let metadata = self.row_group_metadata.as_ref().unwrap().clone();
@@ -504,6 +605,45 @@ impl<T: Write + Position> PageWriter for SerializedPageWriter<T> {
     Ok(spec)
   }
 
+  fn write_raw_page(
+    &mut self,
+    header: parquet::PageHeader,
+    buf: ByteBufferPtr,
+  ) -> Result<PageWriteSpec>
+  {
+    if header.compressed_page_size as usize != buf.len() {
+      return Err(general_err!(
+        "Raw page size mismatch: header has {} bytes, buffer has {} bytes",
+        header.compressed_page_size,
+        buf.len()
+      ));
+    }
+
+    let page_type = PageType::from(header.type_);
+    let uncompressed_size = header.uncompressed_page_size as usize;
+    let compressed_size = header.compressed_page_size as usize;
+    let num_values = match (&header.data_page_header, &header.data_page_header_v2) {
+      (Some(ref data_page_header), _) => data_page_header.num_values as u32,
+      (None, Some(ref data_page_header_v2)) => data_page_header_v2.num_values as u32,
+      (None, None) => 0,
+    };
+
+    let start_pos = self.sink.pos();
+
+    let header_size = self.serialize_page_header(header)?;
+    self.sink.write_all(buf.data())?;
+
+    let mut spec = PageWriteSpec::new();
+    spec.page_type = page_type;
+    spec.uncompressed_size = uncompressed_size + header_size;
+    spec.compressed_size = compressed_size + header_size;
+    spec.offset = start_pos;
+    spec.bytes_written = self.sink.pos() - start_pos;
+    spec.num_values = num_values;
+
+    Ok(spec)
+  }
+
   fn write_metadata(&mut self, metadata: &ColumnChunkMetaData) -> Result<()> {
     self.serialize_column_chunk(metadata.to_thrift())
   }
@@ -516,24 +656,30 @@ impl<T: Write + Position> PageWriter for SerializedPageWriter<T> {
 
 #[cfg(test)]
 mod tests {
-  use std::{error::Error, io::Cursor};
+  use std::{
+    cell::RefCell,
+    error::Error,
+    fs::File,
+    io::{Cursor, Read},
+    rc::Rc,
+  };
 
   use super::*;
   use basic::{Compression, Encoding, Repetition, Type};
-  use column::page::PageReader;
+  use column::{page::PageReader, reader::ColumnReader};
   use compression::{create_codec, Codec};
   use file::{
     properties::WriterProperties,
-    reader::{FileReader, SerializedFileReader, SerializedPageReader},
+    reader::{validate_layout, FileReader, SerializedFileReader, SerializedPageReader},
     statistics::{from_thrift, to_thrift, Statistics},
   };
   use record::RowAccessor;
-  use util::{memory::ByteBufferPtr, test_common::get_temp_file};
+  use util::test_common::{get_temp_file, get_test_file};
 
   #[test]
   fn test_file_writer_error_after_close() {
     let file = get_temp_file("test_file_writer_error_after_close", &[]);
-    let schema = Rc::new(types::Type::group_type_builder("schema").build().unwrap());
+    let schema = Arc::new(types::Type::group_type_builder("schema").build().unwrap());
     let props = Rc::new(WriterProperties::builder().build());
     let mut writer = SerializedFileWriter::new(file, schema, props).unwrap();
     writer.close().unwrap();
@@ -556,7 +702,7 @@ mod tests {
   #[test]
   fn test_row_group_writer_error_after_close() {
     let file = get_temp_file("test_file_writer_row_group_error_after_close", &[]);
-    let schema = Rc::new(types::Type::group_type_builder("schema").build().unwrap());
+    let schema = Arc::new(types::Type::group_type_builder("schema").build().unwrap());
     let props = Rc::new(WriterProperties::builder().build());
     let mut writer = SerializedFileWriter::new(file, schema, props).unwrap();
     let mut row_group_writer = writer.next_row_group().unwrap();
@@ -572,9 +718,9 @@ mod tests {
   #[test]
   fn test_row_group_writer_error_not_all_columns_written() {
     let file = get_temp_file("test_row_group_writer_error_not_all_columns_written", &[]);
-    let schema = Rc::new(
+    let schema = Arc::new(
       types::Type::group_type_builder("schema")
-        .with_fields(&mut vec![Rc::new(
+        .with_fields(&mut vec![Arc::new(
           types::Type::primitive_type_builder("col1", Type::INT32)
             .build()
             .unwrap(),
@@ -595,16 +741,16 @@ mod tests {
   #[test]
   fn test_row_group_writer_num_records_mismatch() {
     let file = get_temp_file("test_row_group_writer_num_records_mismatch", &[]);
-    let schema = Rc::new(
+    let schema = Arc::new(
       types::Type::group_type_builder("schema")
         .with_fields(&mut vec![
-          Rc::new(
+          Arc::new(
             types::Type::primitive_type_builder("col1", Type::INT32)
               .with_repetition(Repetition::REQUIRED)
               .build()
               .unwrap(),
           ),
-          Rc::new(
+          Arc::new(
             types::Type::primitive_type_builder("col2", Type::INT32)
               .with_repetition(Repetition::REQUIRED)
               .build()
@@ -643,9 +789,9 @@ mod tests {
   fn test_file_writer_empty_file() {
     let file = get_temp_file("test_file_writer_write_empty_file", &[]);
 
-    let schema = Rc::new(
+    let schema = Arc::new(
       types::Type::group_type_builder("schema")
-        .with_fields(&mut vec![Rc::new(
+        .with_fields(&mut vec![Arc::new(
           types::Type::primitive_type_builder("col1", Type::INT32)
             .build()
             .unwrap(),
@@ -697,6 +843,178 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_file_writer_open_for_append() {
+    let file = get_temp_file("test_file_writer_open_for_append", &[]);
+    test_file_roundtrip(file.try_clone().unwrap(), vec![vec![1, 2, 3], vec![4, 5]]);
+
+    let props = Rc::new(WriterProperties::builder().build());
+    let mut file_writer =
+      SerializedFileWriter::open_for_append(file.try_clone().unwrap(), props).unwrap();
+    write_row_groups(&mut file_writer, &[vec![6, 7, 8, 9], vec![10]]);
+    file_writer.close().unwrap();
+
+    let reader = SerializedFileReader::new(file.try_clone().unwrap()).unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 10);
+    assert_eq!(validate_layout(&reader).unwrap(), Vec::<String>::new());
+    assert_row_groups(file, &[vec![1, 2, 3], vec![4, 5], vec![6, 7, 8, 9], vec![10]]);
+  }
+
+  #[test]
+  fn test_file_writer_open_for_append_keeps_footer_fields() {
+    let file = get_temp_file("test_file_writer_open_for_append_keeps_footer_fields", &[]);
+    test_file_roundtrip(file.try_clone().unwrap(), vec![vec![1, 2, 3]]);
+
+    // Replace footer with one that has fields the writer does not set by itself
+    let mut data = Vec::new();
+    let mut reader = file.try_clone().unwrap();
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    reader.read_to_end(&mut data).unwrap();
+    let mut t_file_metadata = read_thrift_metadata(&data);
+    t_file_metadata.key_value_metadata = Some(vec![parquet::KeyValue {
+      key: "key".to_owned(),
+      value: Some("value".to_owned()),
+    }]);
+    t_file_metadata.created_by = Some("another writer".to_owned());
+    t_file_metadata.column_orders =
+      Some(vec![parquet::ColumnOrder::TYPEORDER(parquet::TypeDefinedOrder {})]);
+    t_file_metadata.row_groups[0].sorting_columns = Some(vec![parquet::SortingColumn {
+      column_idx: 0,
+      descending: false,
+      nulls_first: true,
+    }]);
+    let metadata_len = LittleEndian::read_i32(&data[data.len() - FOOTER_SIZE..]) as usize;
+    data.truncate(data.len() - FOOTER_SIZE - metadata_len);
+    let mut metadata_buffer = Vec::new();
+    {
+      let mut protocol = TCompactOutputProtocol::new(&mut metadata_buffer);
+      t_file_metadata.write_to_out_protocol(&mut protocol).unwrap();
+      protocol.flush().unwrap();
+    }
+    data.extend_from_slice(&metadata_buffer);
+    let mut footer_buffer = [0; FOOTER_SIZE];
+    LittleEndian::write_i32(&mut footer_buffer, metadata_buffer.len() as i32);
+    footer_buffer[4..].copy_from_slice(&PARQUET_MAGIC);
+    data.extend_from_slice(&footer_buffer);
+    let file = get_temp_file("test_file_writer_append_footer_fields", &data);
+
+    let props = Rc::new(WriterProperties::builder().build());
+    let mut file_writer =
+      SerializedFileWriter::open_for_append(file.try_clone().unwrap(), props).unwrap();
+    write_row_groups(&mut file_writer, &[vec![4, 5]]);
+    file_writer.close().unwrap();
+
+    let mut data = Vec::new();
+    let mut reader = file.try_clone().unwrap();
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    reader.read_to_end(&mut data).unwrap();
+    let appended_metadata = read_thrift_metadata(&data);
+    assert_eq!(appended_metadata.num_rows, 5);
+    assert_eq!(
+      appended_metadata.key_value_metadata,
+      t_file_metadata.key_value_metadata
+    );
+    assert_eq!(appended_metadata.created_by, t_file_metadata.created_by);
+    assert_eq!(appended_metadata.column_orders, t_file_metadata.column_orders);
+    assert_eq!(appended_metadata.row_groups.len(), 2);
+    assert_eq!(appended_metadata.row_groups[0], t_file_metadata.row_groups[0]);
+    assert_row_groups(file, &[vec![1, 2, 3], vec![4, 5]]);
+  }
+
+  #[test]
+  fn test_file_writer_open_for_append_smaller_footer() {
+    // Original footer has more metadata than the writer produces
+    let mut data = Vec::new();
+    get_test_file("alltypes_plain.parquet")
+      .read_to_end(&mut data)
+      .unwrap();
+    let file = get_temp_file("test_file_writer_open_for_append_smaller_footer", &data);
+
+    let props = Rc::new(WriterProperties::builder().build());
+    let mut file_writer =
+      SerializedFileWriter::open_for_append(file.try_clone().unwrap(), props).unwrap();
+    file_writer.close().unwrap();
+
+    // Metadata of the old row group is kept unchanged
+    let mut appended_data = Vec::new();
+    let mut reader = file.try_clone().unwrap();
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    reader.read_to_end(&mut appended_data).unwrap();
+    assert_eq!(
+      read_thrift_metadata(&appended_data).row_groups,
+      read_thrift_metadata(&data).row_groups
+    );
+
+    assert_eq!(file.metadata().unwrap().len(), data.len() as u64);
+    let reader = SerializedFileReader::new(file).unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 8);
+    assert_eq!(validate_layout(&reader).unwrap(), Vec::<String>::new());
+    let row_group_reader = reader.get_row_group(0).unwrap();
+    let ids = match row_group_reader.get_column_reader(0).unwrap() {
+      ColumnReader::Int32ColumnReader(mut typed) => {
+        let mut values = vec![0; 16];
+        let (values_read, _) = typed.read_batch(16, None, None, &mut values).unwrap();
+        values.truncate(values_read);
+        values
+      },
+      _ => panic!("Expected INT32 column"),
+    };
+    assert_eq!(ids, vec![4, 5, 6, 7, 2, 3, 0, 1]);
+  }
+
+  #[test]
+  fn test_file_writer_flushes_row_groups_incrementally() {
+    let sink = TrackingSink::new();
+    let data = vec![vec![1; 1024], vec![2; 100], vec![3; 10]];
+
+    let schema = Arc::new(
+      types::Type::group_type_builder("schema")
+        .with_fields(&mut vec![Arc::new(
+          types::Type::primitive_type_builder("col1", Type::INT32)
+            .with_repetition(Repetition::REQUIRED)
+            .build()
+            .unwrap(),
+        )])
+        .build()
+        .unwrap(),
+    );
+    let props = Rc::new(WriterProperties::builder().build());
+    let mut file_writer = SerializedFileWriter::new(sink.clone(), schema, props).unwrap();
+    assert_eq!(sink.len(), PARQUET_MAGIC.len());
+
+    let mut row_group_end = sink.len();
+    for subset in &data {
+      let mut row_group_writer = file_writer.next_row_group().unwrap();
+      let mut col_writer = row_group_writer.next_column().unwrap().unwrap();
+      if let ColumnWriter::Int32ColumnWriter(ref mut typed) = col_writer {
+        typed.write_batch(&subset[..], None, None).unwrap();
+      }
+      row_group_writer.close_column(col_writer).unwrap();
+      let metadata = row_group_writer.close().unwrap();
+      file_writer.close_row_group(row_group_writer).unwrap();
+
+      // Row group starts exactly where the previous one ended, and all of its bytes
+      // have reached the sink before the next row group is requested.
+      let column = metadata.column(0);
+      let chunk_start = column
+        .dictionary_page_offset()
+        .unwrap_or(column.data_page_offset());
+      assert_eq!(chunk_start as usize, row_group_end);
+      assert!(sink.len() > column.file_offset() as usize);
+      row_group_end = sink.len();
+    }
+
+    // Nothing but the footer is written on close
+    file_writer.close().unwrap();
+    assert!(sink.len() > row_group_end);
+
+    let file =
+      get_temp_file("test_file_writer_flushes_row_groups_incrementally", &sink.data());
+    let reader = SerializedFileReader::new(file).unwrap();
+    assert_eq!(reader.num_row_groups(), data.len());
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 1134);
+  }
+
   #[test]
   fn test_page_writer_data_pages() {
     let pages = vec![
@@ -759,10 +1077,143 @@ mod tests {
     test_page_roundtrip(&pages[..], Compression::UNCOMPRESSED, Type::INT32);
   }
 
+  #[test]
+  fn test_page_writer_raw_pages_copy_through() {
+    let pages = vec![
+      Page::DictionaryPage {
+        buf: ByteBufferPtr::new(vec![1, 2, 3, 4, 5]),
+        num_values: 5,
+        encoding: Encoding::PLAIN,
+        is_sorted: false,
+      },
+      Page::DataPage {
+        buf: ByteBufferPtr::new(vec![1, 2, 3, 4, 5, 6, 7, 8]),
+        num_values: 10,
+        encoding: Encoding::RLE_DICTIONARY,
+        def_level_encoding: Encoding::RLE,
+        rep_level_encoding: Encoding::RLE,
+        statistics: Some(Statistics::int32(Some(1), Some(3), None, 7, true)),
+      },
+      Page::DataPageV2 {
+        buf: ByteBufferPtr::new(vec![4; 128]),
+        num_values: 10,
+        encoding: Encoding::DELTA_BINARY_PACKED,
+        num_nulls: 2,
+        num_rows: 12,
+        def_levels_byte_len: 24,
+        rep_levels_byte_len: 32,
+        is_compressed: true,
+        statistics: None,
+      },
+    ];
+    let (buffer, total_num_values) =
+      write_test_pages(&pages[..], Compression::SNAPPY, Type::INT32);
+
+    // Copy pages without decompressing them
+    let mut copy: Vec<u8> = vec![];
+    let mut num_pages = 0;
+    {
+      let mut page_reader = SerializedPageReader::new(
+        Cursor::new(&buffer),
+        total_num_values,
+        Compression::SNAPPY,
+        Type::INT32,
+      )
+      .unwrap();
+      let mut page_writer = SerializedPageWriter::new(Cursor::new(&mut copy));
+
+      while let Some((header, buf)) = page_reader.next_raw().unwrap() {
+        assert_eq!(header.compressed_page_size as usize, buf.len());
+        let spec = page_writer.write_raw_page(header, buf).unwrap();
+        assert_eq!(spec.page_type, pages[num_pages].page_type());
+        num_pages += 1;
+      }
+      page_writer.close().unwrap();
+    }
+    assert_eq!(num_pages, pages.len());
+    assert_eq!(copy, buffer);
+
+    // Copied pages are decoded into original pages
+    let mut page_reader = SerializedPageReader::new(
+      Cursor::new(&copy),
+      total_num_values,
+      Compression::SNAPPY,
+      Type::INT32,
+    )
+    .unwrap();
+    for page in &pages {
+      assert_page(&page_reader.get_next_page().unwrap().unwrap(), page);
+    }
+    assert!(page_reader.get_next_page().unwrap().is_none());
+  }
+
+  #[test]
+  fn test_page_writer_raw_page_size_mismatch() {
+    let pages = vec![Page::DataPage {
+      buf: ByteBufferPtr::new(vec![1, 2, 3, 4, 5]),
+      num_values: 5,
+      encoding: Encoding::PLAIN,
+      def_level_encoding: Encoding::RLE,
+      rep_level_encoding: Encoding::RLE,
+      statistics: None,
+    }];
+    let (buffer, total_num_values) =
+      write_test_pages(&pages[..], Compression::UNCOMPRESSED, Type::INT32);
+    let mut page_reader = SerializedPageReader::new(
+      Cursor::new(&buffer),
+      total_num_values,
+      Compression::UNCOMPRESSED,
+      Type::INT32,
+    )
+    .unwrap();
+    let (header, _) = page_reader.next_raw().unwrap().unwrap();
+
+    let mut copy: Vec<u8> = vec![];
+    let mut page_writer = SerializedPageWriter::new(Cursor::new(&mut copy));
+    let res = page_writer.write_raw_page(header, ByteBufferPtr::new(vec![1, 2, 3]));
+    assert!(res.is_err());
+    if let Err(err) = res {
+      assert_eq!(
+        format!("{}", err),
+        "Parquet error: Raw page size mismatch: header has 5 bytes, buffer has 3 bytes"
+      );
+    }
+  }
+
   /// Tests writing and reading pages.
   /// Physical type is for statistics only, should match any defined statistics type in
   /// pages.
   fn test_page_roundtrip(pages: &[Page], codec: Compression, physical_type: Type) {
+    let (buffer, total_num_values) = write_test_pages(pages, codec, physical_type);
+    let mut result_pages: Vec<Page> = vec![];
+    {
+      let mut page_reader = SerializedPageReader::new(
+        Cursor::new(&buffer),
+        total_num_values,
+        codec,
+        physical_type,
+      )
+      .unwrap();
+
+      while let Some(page) = page_reader.get_next_page().unwrap() {
+        result_pages.push(page);
+      }
+    }
+
+    assert_eq!(result_pages.len(), pages.len());
+    for i in 0..result_pages.len() {
+      assert_page(&result_pages[i], &pages[i]);
+    }
+  }
+
+  /// Compresses pages with `codec` and writes them into a buffer.
+  /// Returns the buffer and total number of values in data pages.
+  fn write_test_pages(
+    pages: &[Page],
+    codec: Compression,
+    physical_type: Type,
+  ) -> (Vec<u8>, i64)
+  {
     let mut compressed_pages = vec![];
     let mut total_num_values = 0i64;
     let mut compressor = create_codec(codec).unwrap();
@@ -842,7 +1293,6 @@ mod tests {
     }
 
     let mut buffer: Vec<u8> = vec![];
-    let mut result_pages: Vec<Page> = vec![];
     {
       let cursor = Cursor::new(&mut buffer);
       let mut page_writer = SerializedPageWriter::new(cursor);
@@ -852,24 +1302,7 @@ mod tests {
       }
       page_writer.close().unwrap();
     }
-    {
-      let mut page_reader = SerializedPageReader::new(
-        Cursor::new(&buffer),
-        total_num_values,
-        codec,
-        physical_type,
-      )
-      .unwrap();
-
-      while let Some(page) = page_reader.get_next_page().unwrap() {
-        result_pages.push(page);
-      }
-    }
-
-    assert_eq!(result_pages.len(), pages.len());
-    for i in 0..result_pages.len() {
-      assert_page(&result_pages[i], &pages[i]);
-    }
+    (buffer, total_num_values)
   }
 
   /// Helper function to compress a slice
@@ -892,12 +1325,49 @@ mod tests {
     assert_eq!(to_thrift(left.statistics()), to_thrift(right.statistics()));
   }
 
+  /// In-memory sink that exposes how many bytes have been written into it.
+  /// Clones share the same buffer and position, similar to `File::try_clone`.
+  #[derive(Clone)]
+  struct TrackingSink {
+    inner: Rc<RefCell<Cursor<Vec<u8>>>>,
+  }
+
+  impl TrackingSink {
+    fn new() -> Self {
+      Self {
+        inner: Rc::new(RefCell::new(Cursor::new(Vec::new()))),
+      }
+    }
+
+    fn len(&self) -> usize { self.inner.borrow().get_ref().len() }
+
+    fn data(&self) -> Vec<u8> { self.inner.borrow().get_ref().clone() }
+  }
+
+  impl Write for TrackingSink {
+    fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize> {
+      self.inner.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> ::std::io::Result<()> { self.inner.borrow_mut().flush() }
+  }
+
+  impl Seek for TrackingSink {
+    fn seek(&mut self, pos: SeekFrom) -> ::std::io::Result<u64> {
+      self.inner.borrow_mut().seek(pos)
+    }
+  }
+
+  impl TryClone for TrackingSink {
+    fn try_clone(&self) -> Result<Self> { Ok(self.clone()) }
+  }
+
   /// File write-read roundtrip.
   /// `data` consists of arrays of values for each row group.
   fn test_file_roundtrip(file: File, data: Vec<Vec<i32>>) {
-    let schema = Rc::new(
+    let schema = Arc::new(
       types::Type::group_type_builder("schema")
-        .with_fields(&mut vec![Rc::new(
+        .with_fields(&mut vec![Arc::new(
           types::Type::primitive_type_builder("col1", Type::INT32)
             .with_repetition(Repetition::REQUIRED)
             .build()
@@ -909,8 +1379,15 @@ mod tests {
     let props = Rc::new(WriterProperties::builder().build());
     let mut file_writer =
       SerializedFileWriter::new(file.try_clone().unwrap(), schema, props).unwrap();
+    write_row_groups(&mut file_writer, &data);
+    file_writer.close().unwrap();
 
-    for subset in &data {
+    assert_row_groups(file, &data);
+  }
+
+  /// Writes row groups of a single INT32 column.
+  fn write_row_groups(file_writer: &mut SerializedFileWriter<File>, data: &[Vec<i32>]) {
+    for subset in data {
       let mut row_group_writer = file_writer.next_row_group().unwrap();
       let mut col_writer = row_group_writer.next_column().unwrap();
       if let Some(mut writer) = col_writer {
@@ -926,9 +1403,18 @@ mod tests {
       }
       file_writer.close_row_group(row_group_writer).unwrap();
     }
+  }
 
-    file_writer.close().unwrap();
+  /// Decodes Thrift file metadata from the footer of file `data`.
+  fn read_thrift_metadata(data: &[u8]) -> parquet::FileMetaData {
+    let metadata_len = LittleEndian::read_i32(&data[data.len() - FOOTER_SIZE..]) as usize;
+    let metadata_start = data.len() - FOOTER_SIZE - metadata_len;
+    let mut prot = TCompactInputProtocol::new(&data[metadata_start..]);
+    parquet::FileMetaData::read_from_in_protocol(&mut prot).unwrap()
+  }
 
+  /// Checks that row groups of a single INT32 column contain `data`.
+  fn assert_row_groups(file: File, data: &[Vec<i32>]) {
     let reader = SerializedFileReader::new(file).unwrap();
     assert_eq!(reader.num_row_groups(), data.len());
     for i in 0..reader.num_row_groups() {
@@ -21,9 +21,8 @@ use std::fmt;
 
 use basic::{LogicalType, Type as PhysicalType};
 use chrono::{Local, TimeZone};
-use data_type::{ByteArray, Decimal, Int96};
+use data_type::{ByteArray, Decimal, Int96, Interval};
 use errors::{ParquetError, Result};
-use num_bigint::{BigInt, Sign};
 use schema::types::ColumnDescPtr;
 
 /// Macro as a shortcut to generate 'not yet implemented' panic error.
@@ -47,6 +46,17 @@ pub struct Row {
 impl Row {
   /// Get the number of fields in this row.
   pub fn len(&self) -> usize { self.fields.len() }
+
+  /// Returns decimal value of the field with the given `name`.
+  ///
+  /// Returns error if there is no such field or the field is not a decimal.
+  /// Use `to_string()` on the result to render the scaled value, e.g. "12.34".
+  pub fn get_decimal_by_name(&self, name: &str) -> Result<&Decimal> {
+    match self.fields.iter().position(|&(ref key, _)| key == name) {
+      Some(i) => self.get_decimal(i),
+      None => Err(general_err!("Field {} does not exist", name)),
+    }
+  }
 }
 
 /// Trait for type-safe convenient access to fields within a Row.
@@ -63,6 +73,7 @@ pub trait RowAccessor {
   fn get_float(&self, i: usize) -> Result<f32>;
   fn get_double(&self, i: usize) -> Result<f64>;
   fn get_timestamp(&self, i: usize) -> Result<u64>;
+  fn get_interval(&self, i: usize) -> Result<Interval>;
   fn get_decimal(&self, i: usize) -> Result<&Decimal>;
   fn get_string(&self, i: usize) -> Result<&String>;
   fn get_bytes(&self, i: usize) -> Result<&ByteArray>;
@@ -124,6 +135,8 @@ impl RowAccessor for Row {
 
   row_primitive_accessor!(get_timestamp, Timestamp, u64);
 
+  row_primitive_accessor!(get_interval, Interval, Interval);
+
   row_complex_accessor!(get_decimal, Decimal, Decimal);
 
   row_complex_accessor!(get_string, Str, String);
@@ -186,6 +199,7 @@ pub trait ListAccessor {
   fn get_float(&self, i: usize) -> Result<f32>;
   fn get_double(&self, i: usize) -> Result<f64>;
   fn get_timestamp(&self, i: usize) -> Result<u64>;
+  fn get_interval(&self, i: usize) -> Result<Interval>;
   fn get_decimal(&self, i: usize) -> Result<&Decimal>;
   fn get_string(&self, i: usize) -> Result<&String>;
   fn get_bytes(&self, i: usize) -> Result<&ByteArray>;
@@ -251,6 +265,8 @@ impl ListAccessor for List {
 
   list_primitive_accessor!(get_timestamp, Timestamp, u64);
 
+  list_primitive_accessor!(get_interval, Interval, Interval);
+
   list_complex_accessor!(get_decimal, Decimal, Decimal);
 
   list_complex_accessor!(get_string, Str, String);
@@ -330,6 +346,8 @@ impl<'a> ListAccessor for MapList<'a> {
 
   map_list_primitive_accessor!(get_timestamp, Timestamp, u64);
 
+  map_list_primitive_accessor!(get_interval, Interval, Interval);
+
   list_complex_accessor!(get_decimal, Decimal, Decimal);
 
   list_complex_accessor!(get_string, Str, String);
@@ -398,6 +416,8 @@ pub enum Field {
   Date(u32),
   /// Milliseconds from the Unix epoch, 1 January 1970.
   Timestamp(u64),
+  /// Interval of months, days and milliseconds.
+  Interval(Interval),
 
   // ----------------------------------------------------------------------
   // Complex types
@@ -430,6 +450,7 @@ impl Field {
       Field::Str(_) => "Str",
       Field::Bytes(_) => "Bytes",
       Field::Timestamp(_) => "Timestamp",
+      Field::Interval(_) => "Interval",
       Field::Group(_) => "Group",
       Field::ListInternal(_) => "ListInternal",
       Field::MapInternal(_) => "MapInternal",
@@ -546,6 +567,7 @@ impl Field {
           descr.type_precision(),
           descr.type_scale(),
         )),
+        LogicalType::INTERVAL => Field::Interval(Interval::from_bytes(value.data())),
         LogicalType::NONE => Field::Bytes(value),
         _ => nyi!(descr, value),
       },
@@ -581,11 +603,18 @@ impl fmt::Display for Field {
           write!(f, "{:?}", value)
         }
       },
-      Field::Decimal(ref value) => write!(f, "{}", convert_decimal_to_string(value)),
+      Field::Decimal(ref value) => write!(f, "{}", value),
       Field::Str(ref value) => write!(f, "\"{}\"", value),
       Field::Bytes(ref value) => write!(f, "{:?}", value.data()),
       Field::Date(value) => write!(f, "{}", convert_date_to_string(value)),
       Field::Timestamp(value) => write!(f, "{}", convert_timestamp_to_string(value)),
+      Field::Interval(value) => write!(
+        f,
+        "{} months {} days {} millis",
+        value.months(),
+        value.days(),
+        value.millis()
+      ),
       Field::Group(ref fields) => write!(f, "{}", fields),
       Field::ListInternal(ref list) => {
         let elems = &list.elements;
@@ -634,40 +663,9 @@ fn convert_timestamp_to_string(value: u64) -> String {
   format!("{}", dt.format("%Y-%m-%d %H:%M:%S %:z"))
 }
 
-/// Helper method to convert Parquet decimal into a string.
-/// We assert that `scale >= 0` and `precision > scale`, but this will be enforced
-/// when constructing Parquet schema.
-#[inline]
-fn convert_decimal_to_string(decimal: &Decimal) -> String {
-  assert!(decimal.scale() >= 0 && decimal.precision() > decimal.scale());
-
-  // Specify as signed bytes to resolve sign as part of conversion.
-  let num = BigInt::from_signed_bytes_be(decimal.data());
-
-  // Offset of the first digit in a string.
-  let negative = if num.sign() == Sign::Minus { 1 } else { 0 };
-  let mut num_str = num.to_string();
-  let mut point = num_str.len() as i32 - decimal.scale() - negative;
-
-  // Convert to string form without scientific notation.
-  if point <= 0 {
-    // Zeros need to be prepended to the unscaled value.
-    while point < 0 {
-      num_str.insert(negative as usize, '0');
-      point += 1;
-    }
-    num_str.insert_str(negative as usize, "0.");
-  } else {
-    // No zeroes need to be prepended to the unscaled value, simply insert decimal point.
-    num_str.insert((point + negative) as usize, '.');
-  }
-
-  num_str
-}
-
 #[cfg(test)]
 mod tests {
-  use std::rc::Rc;
+  use std::sync::Arc;
 
   use super::*;
   use chrono;
@@ -680,8 +678,8 @@ mod tests {
         .with_logical_type($logical_type)
         .build()
         .unwrap();
-      Rc::new(ColumnDescriptor::new(
-        Rc::new(tpe),
+      Arc::new(ColumnDescriptor::new(
+        Arc::new(tpe),
         None,
         0,
         0,
@@ -696,8 +694,8 @@ mod tests {
         .with_scale($scale)
         .build()
         .unwrap();
-      Rc::new(ColumnDescriptor::new(
-        Rc::new(tpe),
+      Arc::new(ColumnDescriptor::new(
+        Arc::new(tpe),
         None,
         0,
         0,
@@ -871,6 +869,18 @@ mod tests {
     let row = Field::convert_byte_array(&descr, value.clone());
     assert_eq!(row, Field::Decimal(Decimal::from_bytes(value, 17, 5)));
 
+    // INTERVAL (FIXED_LEN_BYTE_ARRAY)
+    let descr = make_column_descr![
+      PhysicalType::FIXED_LEN_BYTE_ARRAY,
+      LogicalType::INTERVAL,
+      12,
+      0,
+      0
+    ];
+    let value = ByteArray::from(vec![1, 0, 0, 0, 15, 0, 0, 0, 232, 3, 0, 0]);
+    let row = Field::convert_byte_array(&descr, value);
+    assert_eq!(row, Field::Interval(Interval::new(1, 15, 1000)));
+
     // NONE (FIXED_LEN_BYTE_ARRAY)
     let descr = make_column_descr![
       PhysicalType::FIXED_LEN_BYTE_ARRAY,
@@ -949,32 +959,23 @@ mod tests {
   }
 
   #[test]
-  fn test_convert_decimal_to_string() {
-    // Helper method to compare decimal
-    fn check_decimal(bytes: Vec<u8>, precision: i32, scale: i32, res: &str) {
-      let decimal = Decimal::from_bytes(ByteArray::from(bytes), precision, scale);
-      assert_eq!(convert_decimal_to_string(&decimal), res);
-    }
+  fn test_row_get_decimal_by_name() {
+    let row = make_row(vec![
+      ("a".to_string(), Field::Int(1)),
+      ("b".to_string(), Field::Decimal(Decimal::from_i32(1234, 4, 2))),
+    ]);
+    let decimal = row.get_decimal_by_name("b").unwrap();
+    assert_eq!(decimal, &Decimal::from_i32(1234, 4, 2));
+    assert_eq!(decimal.to_string(), "12.34");
 
-    // This example previously used to fail in some engines
-    check_decimal(
-      vec![0, 0, 0, 0, 0, 0, 0, 0, 13, 224, 182, 179, 167, 100, 0, 0],
-      38,
-      18,
-      "1.000000000000000000",
+    assert_eq!(
+      row.get_decimal_by_name("a").unwrap_err(),
+      general_err!("Cannot access Int as Decimal")
     );
-    check_decimal(
-      vec![
-        249, 233, 247, 16, 185, 192, 202, 223, 215, 165, 192, 166, 67, 72,
-      ],
-      36,
-      28,
-      "-12344.0242342304923409234234293432",
+    assert_eq!(
+      row.get_decimal_by_name("c").unwrap_err(),
+      general_err!("Field c does not exist")
     );
-    check_decimal(vec![0, 0, 0, 0, 0, 4, 147, 224], 17, 5, "3.00000");
-    check_decimal(vec![0, 0, 0, 0, 1, 201, 195, 140], 18, 2, "300000.12");
-    check_decimal(vec![207, 200], 10, 2, "-123.44");
-    check_decimal(vec![207, 200], 10, 8, "-0.00012344");
   }
 
   #[test]
@@ -1010,7 +1011,11 @@ mod tests {
     );
     assert_eq!(
       format!("{}", Field::Decimal(Decimal::from_i32(4, 8, 2))),
-      convert_decimal_to_string(&Decimal::from_i32(4, 8, 2))
+      "0.04"
+    );
+    assert_eq!(
+      format!("{}", Field::Interval(Interval::new(1, 2, 3))),
+      "1 months 2 days 3 millis"
     );
 
     // Complex types
@@ -1061,6 +1066,7 @@ mod tests {
     assert!(Field::Bytes(ByteArray::from(vec![1, 2, 3])).is_primitive());
     assert!(Field::Timestamp(12345678).is_primitive());
     assert!(Field::Decimal(Decimal::from_i32(4, 8, 2)).is_primitive());
+    assert!(Field::Interval(Interval::new(1, 2, 3)).is_primitive());
 
     // complex types
     assert_eq!(
@@ -1118,6 +1124,7 @@ mod tests {
         Field::Bytes(ByteArray::from(vec![1, 2, 3, 4, 5])),
       ),
       ("o".to_string(), Field::Decimal(Decimal::from_i32(4, 7, 2))),
+      ("p".to_string(), Field::Interval(Interval::new(1, 2, 3))),
     ]);
 
     assert_eq!(false, row.get_bool(1).unwrap());
@@ -1134,6 +1141,7 @@ mod tests {
     assert_eq!("abc", row.get_string(12).unwrap());
     assert_eq!(5, row.get_bytes(13).unwrap().len());
     assert_eq!(7, row.get_decimal(14).unwrap().precision());
+    assert_eq!(2, row.get_interval(15).unwrap().days());
   }
 
   #[test]
@@ -1290,6 +1298,9 @@ mod tests {
 
     let list = make_list(vec![Field::Decimal(Decimal::from_i32(4, 5, 2))]);
     assert_eq!(&[0, 0, 0, 4], list.get_decimal(0).unwrap().data());
+
+    let list = make_list(vec![Field::Interval(Interval::new(1, 2, 3))]);
+    assert_eq!(3, list.get_interval(0).unwrap().millis());
   }
 
   #[test]
@@ -42,20 +42,32 @@
 //! println!("{:?}", schema);
 //! ```
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use basic::{LogicalType, Repetition, Type as PhysicalType};
 use errors::{ParquetError, Result};
-use schema::types::{Type, TypePtr};
+use schema::types::{Type, TypePtr, DEFAULT_MAX_NESTING_DEPTH};
 
 /// Parses message type as string into a Parquet [`Type`](`::schema::types::Type`) which,
 /// for example, could be used to extract individual columns. Returns Parquet general
-/// error when parsing or validation fails.
+/// error when parsing or validation fails, or when message type is nested deeper than
+/// [`DEFAULT_MAX_NESTING_DEPTH`](`::schema::types::DEFAULT_MAX_NESTING_DEPTH`).
 pub fn parse_message_type<'a>(message_type: &'a str) -> Result<Type> {
-  let mut parser = Parser {
-    tokenizer: &mut Tokenizer::from_str(message_type),
-  };
-  parser.parse_message_type()
+  parse_message_type_with_max_depth(message_type, DEFAULT_MAX_NESTING_DEPTH)
+}
+
+/// Parses message type as string into a Parquet [`Type`](`::schema::types::Type`).
+/// Returns Parquet general error when parsing or validation fails, or when message type
+/// is nested deeper than `max_depth`.
+pub fn parse_message_type_with_max_depth<'a>(
+  message_type: &'a str,
+  max_depth: usize,
+) -> Result<Type>
+{
+  let mut tokenizer = Tokenizer::from_str(message_type);
+  Parser::new(&mut tokenizer)
+    .with_max_depth(max_depth)
+    .parse_message_type()
 }
 
 /// Tokenizer to split message type string into tokens that are separated using characters
@@ -129,6 +141,9 @@ impl<'a> Iterator for Tokenizer<'a> {
 /// recursively.
 struct Parser<'a> {
   tokenizer: &'a mut Tokenizer<'a>,
+  // Nesting depth of the types currently being parsed, root message type has depth 0
+  depth: usize,
+  max_depth: usize,
 }
 
 // Utility function to assert token on validity.
@@ -160,6 +175,22 @@ fn parse_i32(
 }
 
 impl<'a> Parser<'a> {
+  // Creates parser with default maximum nesting depth.
+  fn new(tokenizer: &'a mut Tokenizer<'a>) -> Self {
+    Self {
+      tokenizer,
+      depth: 0,
+      max_depth: DEFAULT_MAX_NESTING_DEPTH,
+    }
+  }
+
+  // Sets maximum nesting depth of types, depth is counted the same way as in
+  // `from_thrift_with_max_depth`.
+  fn with_max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = max_depth;
+    self
+  }
+
   // Entry function to parse message type, uses internal tokenizer.
   fn parse_message_type(&mut self) -> Result<Type> {
     // Check that message type starts with "message".
@@ -182,19 +213,28 @@ impl<'a> Parser<'a> {
   // This is only invoked on root and group types.
   fn parse_child_types(&mut self) -> Result<Vec<TypePtr>> {
     assert_token(self.tokenizer.next(), "{")?;
+    self.depth += 1;
     let mut vec = Vec::new();
     while let Some(value) = self.tokenizer.next() {
       if value == "}" {
         break;
       } else {
         self.tokenizer.backtrack();
-        vec.push(Rc::new(self.add_type()?));
+        vec.push(Arc::new(self.add_type()?));
       }
     }
+    self.depth -= 1;
     Ok(vec)
   }
 
   fn add_type(&mut self) -> Result<Type> {
+    if self.depth > self.max_depth {
+      return Err(general_err!(
+        "Schema nesting depth exceeds maximum of {}",
+        self.max_depth
+      ));
+    }
+
     // Parse repetition
     let repetition = self
       .tokenizer
@@ -449,10 +489,7 @@ mod tests {
   #[test]
   fn test_parse_message_type_invalid() {
     let mut iter = Tokenizer::from_str("test");
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_err());
     assert_eq!(
       result.unwrap_err().to_string(),
@@ -460,13 +497,65 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_parse_message_type_max_depth() {
+    // Schema with 3 nested groups, leaf field has depth 4
+    let schema = "
+    message root {
+      REQUIRED group a {
+        REQUIRED group b {
+          REQUIRED group c {
+            REQUIRED INT32 d;
+          }
+        }
+      }
+    }
+    ";
+    assert!(parse_message_type_with_max_depth(schema, 4).is_ok());
+    assert_eq!(
+      parse_message_type_with_max_depth(schema, 3).unwrap_err(),
+      general_err!("Schema nesting depth exceeds maximum of 3")
+    );
+
+    // Depth counts types, not braces, so empty group `b` has depth 2 as in
+    // `from_thrift_with_max_depth`
+    let schema = "
+    message root {
+      REQUIRED group a {
+        REQUIRED group b {
+        }
+      }
+    }
+    ";
+    assert!(parse_message_type_with_max_depth(schema, 2).is_ok());
+    assert_eq!(
+      parse_message_type_with_max_depth(schema, 1).unwrap_err(),
+      general_err!("Schema nesting depth exceeds maximum of 1")
+    );
+
+    // Pathologically nested schema fails at the default limit
+    let depth = 100_000;
+    let mut schema = String::from("message root {");
+    for i in 0..depth {
+      schema.push_str(&format!(" REQUIRED group g{} {{", i));
+    }
+    schema.push_str(" REQUIRED INT32 leaf;");
+    for _ in 0..depth + 1 {
+      schema.push_str(" }");
+    }
+    assert_eq!(
+      parse_message_type(&schema).unwrap_err(),
+      general_err!(
+        "Schema nesting depth exceeds maximum of {}",
+        DEFAULT_MAX_NESTING_DEPTH
+      )
+    );
+  }
+
   #[test]
   fn test_parse_message_type_no_name() {
     let mut iter = Tokenizer::from_str("message");
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_err());
     assert_eq!(
       result.unwrap_err().to_string(),
@@ -482,10 +571,7 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_err());
 
     let schema = "
@@ -494,10 +580,7 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_ok());
   }
 
@@ -513,10 +596,7 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_err());
 
     // Invalid decimal, need precision and scale
@@ -526,10 +606,7 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_err());
 
     // Invalid decimal because of `,` - has precision, needs scale
@@ -539,10 +616,7 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_err());
 
     // Invalid decimal because, we always require either precision or scale to be
@@ -553,10 +627,7 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_err());
 
     // Valid decimal (precision, scale)
@@ -567,10 +638,7 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let result = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type();
+    let result = Parser::new(&mut iter).parse_message_type();
     assert!(result.is_ok());
   }
 
@@ -583,15 +651,11 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let message = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type()
-    .unwrap();
+    let message = Parser::new(&mut iter).parse_message_type().unwrap();
 
     let expected = Type::group_type_builder("root")
       .with_fields(&mut vec![
-        Rc::new(
+        Arc::new(
           Type::primitive_type_builder("f1", PhysicalType::FIXED_LEN_BYTE_ARRAY)
             .with_logical_type(LogicalType::DECIMAL)
             .with_length(5)
@@ -600,7 +664,7 @@ mod tests {
             .build()
             .unwrap(),
         ),
-        Rc::new(
+        Arc::new(
           Type::primitive_type_builder("f2", PhysicalType::FIXED_LEN_BYTE_ARRAY)
             .with_logical_type(LogicalType::DECIMAL)
             .with_length(16)
@@ -635,22 +699,18 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let message = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type()
-    .unwrap();
+    let message = Parser::new(&mut iter).parse_message_type().unwrap();
 
     let expected = Type::group_type_builder("root")
-      .with_fields(&mut vec![Rc::new(
+      .with_fields(&mut vec![Arc::new(
         Type::group_type_builder("a0")
           .with_repetition(Repetition::REQUIRED)
           .with_fields(&mut vec![
-            Rc::new(
+            Arc::new(
               Type::group_type_builder("a1")
                 .with_repetition(Repetition::OPTIONAL)
                 .with_logical_type(LogicalType::LIST)
-                .with_fields(&mut vec![Rc::new(
+                .with_fields(&mut vec![Arc::new(
                   Type::primitive_type_builder("a2", PhysicalType::BYTE_ARRAY)
                     .with_repetition(Repetition::REPEATED)
                     .with_logical_type(LogicalType::UTF8)
@@ -660,20 +720,20 @@ mod tests {
                 .build()
                 .unwrap(),
             ),
-            Rc::new(
+            Arc::new(
               Type::group_type_builder("b1")
                 .with_repetition(Repetition::OPTIONAL)
                 .with_logical_type(LogicalType::LIST)
-                .with_fields(&mut vec![Rc::new(
+                .with_fields(&mut vec![Arc::new(
                   Type::group_type_builder("b2")
                     .with_repetition(Repetition::REPEATED)
                     .with_fields(&mut vec![
-                      Rc::new(
+                      Arc::new(
                         Type::primitive_type_builder("b3", PhysicalType::INT32)
                           .build()
                           .unwrap(),
                       ),
-                      Rc::new(
+                      Arc::new(
                         Type::primitive_type_builder("b4", PhysicalType::DOUBLE)
                           .build()
                           .unwrap(),
@@ -708,46 +768,42 @@ mod tests {
     }
     ";
     let mut iter = Tokenizer::from_str(schema);
-    let message = Parser {
-      tokenizer: &mut iter,
-    }
-    .parse_message_type()
-    .unwrap();
+    let message = Parser::new(&mut iter).parse_message_type().unwrap();
 
     let mut fields = vec![
-      Rc::new(
+      Arc::new(
         Type::primitive_type_builder("_1", PhysicalType::INT32)
           .with_repetition(Repetition::REQUIRED)
           .with_logical_type(LogicalType::INT_8)
           .build()
           .unwrap(),
       ),
-      Rc::new(
+      Arc::new(
         Type::primitive_type_builder("_2", PhysicalType::INT32)
           .with_repetition(Repetition::REQUIRED)
           .with_logical_type(LogicalType::INT_16)
           .build()
           .unwrap(),
       ),
-      Rc::new(
+      Arc::new(
         Type::primitive_type_builder("_3", PhysicalType::FLOAT)
           .with_repetition(Repetition::REQUIRED)
           .build()
           .unwrap(),
       ),
-      Rc::new(
+      Arc::new(
         Type::primitive_type_builder("_4", PhysicalType::DOUBLE)
           .with_repetition(Repetition::REQUIRED)
           .build()
           .unwrap(),
       ),
-      Rc::new(
+      Arc::new(
         Type::primitive_type_builder("_5", PhysicalType::INT32)
           .with_logical_type(LogicalType::DATE)
           .build()
           .unwrap(),
       ),
-      Rc::new(
+      Arc::new(
         Type::primitive_type_builder("_6", PhysicalType::BYTE_ARRAY)
           .with_logical_type(LogicalType::UTF8)
           .build()
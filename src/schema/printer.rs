@@ -255,7 +255,7 @@ impl<'a> Printer<'a> {
 
 #[cfg(test)]
 mod tests {
-  use std::rc::Rc;
+  use std::sync::Arc;
 
   use super::*;
   use basic::{Repetition, Type as PhysicalType};
@@ -321,8 +321,8 @@ mod tests {
         .with_id(2)
         .build();
       let mut struct_fields = Vec::new();
-      struct_fields.push(Rc::new(f1.unwrap()));
-      struct_fields.push(Rc::new(f2.unwrap()));
+      struct_fields.push(Arc::new(f1.unwrap()));
+      struct_fields.push(Arc::new(f2.unwrap()));
       let foo = Type::group_type_builder("foo")
         .with_repetition(Repetition::OPTIONAL)
         .with_fields(&mut struct_fields)
@@ -330,8 +330,8 @@ mod tests {
         .build()
         .unwrap();
       let mut fields = Vec::new();
-      fields.push(Rc::new(foo));
-      fields.push(Rc::new(f3.unwrap()));
+      fields.push(Arc::new(foo));
+      fields.push(Arc::new(f3.unwrap()));
       let message = Type::group_type_builder("schema")
         .with_fields(&mut fields)
         .with_id(2)
@@ -360,7 +360,7 @@ mod tests {
     let a1 = Type::group_type_builder("a1")
       .with_repetition(Repetition::OPTIONAL)
       .with_logical_type(LogicalType::LIST)
-      .with_fields(&mut vec![Rc::new(a2)])
+      .with_fields(&mut vec![Arc::new(a2)])
       .build()
       .unwrap();
 
@@ -377,25 +377,25 @@ mod tests {
     let b2 = Type::group_type_builder("b2")
       .with_repetition(Repetition::REPEATED)
       .with_logical_type(LogicalType::NONE)
-      .with_fields(&mut vec![Rc::new(b3), Rc::new(b4)])
+      .with_fields(&mut vec![Arc::new(b3), Arc::new(b4)])
       .build()
       .unwrap();
 
     let b1 = Type::group_type_builder("b1")
       .with_repetition(Repetition::OPTIONAL)
       .with_logical_type(LogicalType::LIST)
-      .with_fields(&mut vec![Rc::new(b2)])
+      .with_fields(&mut vec![Arc::new(b2)])
       .build()
       .unwrap();
 
     let a0 = Type::group_type_builder("a0")
       .with_repetition(Repetition::REQUIRED)
-      .with_fields(&mut vec![Rc::new(a1), Rc::new(b1)])
+      .with_fields(&mut vec![Arc::new(a1), Arc::new(b1)])
       .build()
       .unwrap();
 
     let message = Type::group_type_builder("root")
-      .with_fields(&mut vec![Rc::new(a0)])
+      .with_fields(&mut vec![Arc::new(a0)])
       .build()
       .unwrap();
 
@@ -418,7 +418,7 @@ mod tests {
 
     let foo = Type::group_type_builder("foo")
       .with_repetition(Repetition::OPTIONAL)
-      .with_fields(&mut vec![Rc::new(f1), Rc::new(f2)])
+      .with_fields(&mut vec![Arc::new(f1), Arc::new(f2)])
       .build()
       .unwrap();
 
@@ -430,7 +430,7 @@ mod tests {
       .unwrap();
 
     let message = Type::group_type_builder("schema")
-      .with_fields(&mut vec![Rc::new(foo), Rc::new(f3)])
+      .with_fields(&mut vec![Arc::new(foo), Arc::new(f3)])
       .build()
       .unwrap();
 
@@ -456,7 +456,7 @@ mod tests {
       .unwrap();
 
     let message = Type::group_type_builder("schema")
-      .with_fields(&mut vec![Rc::new(f1), Rc::new(f2)])
+      .with_fields(&mut vec![Arc::new(f1), Arc::new(f2)])
       .build()
       .unwrap();
 
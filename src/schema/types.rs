@@ -17,7 +17,7 @@
 
 //! Contains structs and methods to build Parquet schema and schema descriptors.
 
-use std::{collections::HashMap, convert::From, fmt, rc::Rc};
+use std::{collections::HashMap, convert::From, fmt, sync::Arc};
 
 use basic::{LogicalType, Repetition, Type as PhysicalType};
 use errors::{ParquetError, Result};
@@ -26,12 +26,17 @@ use parquet_format::SchemaElement;
 // ----------------------------------------------------------------------
 // Parquet Type definitions
 
-/// Type alias for `Rc<Type>`.
-pub type TypePtr = Rc<Type>;
-/// Type alias for `Rc<SchemaDescriptor>`.
-pub type SchemaDescPtr = Rc<SchemaDescriptor>;
-/// Type alias for `Rc<ColumnDescriptor>`.
-pub type ColumnDescPtr = Rc<ColumnDescriptor>;
+/// Default maximum nesting depth of a schema accepted when parsing message type or
+/// converting schema from Thrift. Root message type has depth 0, its fields have
+/// depth 1, and so on.
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 100;
+
+/// Type alias for `Arc<Type>`.
+pub type TypePtr = Arc<Type>;
+/// Type alias for `Arc<SchemaDescriptor>`.
+pub type SchemaDescPtr = Arc<SchemaDescriptor>;
+/// Type alias for `Arc<ColumnDescriptor>`.
+pub type ColumnDescPtr = Arc<ColumnDescriptor>;
 
 /// Representation of a Parquet type.
 /// Used to describe primitive leaf fields and structs, including top-level schema.
@@ -731,6 +736,9 @@ impl SchemaDescriptor {
   /// Returns schema as [`Type`](`::schema::types::Type`).
   pub fn root_schema(&self) -> &Type { self.schema.as_ref() }
 
+  /// Returns reference counted clone of schema [`Type`](`::schema::types::Type`).
+  pub fn root_schema_ptr(&self) -> TypePtr { self.schema.clone() }
+
   /// Returns schema name.
   pub fn name(&self) -> &str { self.schema.name() }
 }
@@ -764,7 +772,7 @@ fn build_tree(
     &Type::PrimitiveType { .. } => {
       let mut path: Vec<String> = vec![];
       path.extend_from_slice(&path_so_far[..]);
-      leaves.push(Rc::new(ColumnDescriptor::new(
+      leaves.push(Arc::new(ColumnDescriptor::new(
         tp.clone(),
         Some(root_tp),
         max_def_level,
@@ -793,11 +801,23 @@ fn build_tree(
 }
 
 /// Method to convert from Thrift.
+/// Returns error if schema is nested deeper than [`DEFAULT_MAX_NESTING_DEPTH`].
 pub fn from_thrift(elements: &[SchemaElement]) -> Result<TypePtr> {
+  from_thrift_with_max_depth(elements, DEFAULT_MAX_NESTING_DEPTH)
+}
+
+/// Method to convert from Thrift.
+/// Returns error if schema is nested deeper than `max_depth`, this guards against
+/// running out of stack when converting schema of untrusted files.
+pub fn from_thrift_with_max_depth(
+  elements: &[SchemaElement],
+  max_depth: usize,
+) -> Result<TypePtr>
+{
   let mut index = 0;
   let mut schema_nodes = Vec::new();
   while index < elements.len() {
-    let t = from_thrift_helper(elements, index)?;
+    let t = from_thrift_helper(elements, index, 0, max_depth)?;
     index = t.0;
     schema_nodes.push(t.1);
   }
@@ -815,15 +835,25 @@ pub fn from_thrift(elements: &[SchemaElement]) -> Result<TypePtr> {
 /// The first result is the starting index for the next Type after this one. If it is
 /// equal to `elements.len()`, then this Type is the last one.
 /// The second result is the result Type.
+/// `depth` is nesting depth of the Type, which cannot exceed `max_depth`.
 fn from_thrift_helper(
   elements: &[SchemaElement],
   index: usize,
+  depth: usize,
+  max_depth: usize,
 ) -> Result<(usize, TypePtr)>
 {
   // Whether or not the current node is root (message type).
   // There is only one message type node in the schema tree.
   let is_root_node = index == 0;
 
+  if depth > max_depth {
+    return Err(general_err!(
+      "Schema nesting depth exceeds maximum of {}",
+      max_depth
+    ));
+  }
+
   if index > elements.len() {
     return Err(general_err!(
       "Index out of bound, index = {}, len = {}",
@@ -861,14 +891,15 @@ fn from_thrift_helper(
       if let Some(id) = field_id {
         builder = builder.with_id(id);
       }
-      Ok((index + 1, Rc::new(builder.build()?)))
+      Ok((index + 1, Arc::new(builder.build()?)))
     },
     Some(n) => {
       let repetition = elements[index].repetition_type.map(|r| Repetition::from(r));
       let mut fields = vec![];
       let mut next_index = index + 1;
       for _ in 0..n {
-        let child_result = from_thrift_helper(elements, next_index as usize)?;
+        let child_result =
+          from_thrift_helper(elements, next_index as usize, depth + 1, max_depth)?;
         next_index = child_result.0;
         fields.push(child_result.1);
       }
@@ -891,7 +922,7 @@ fn from_thrift_helper(
       if let Some(id) = field_id {
         builder = builder.with_id(id);
       }
-      Ok((next_index, Rc::new(builder.build().unwrap())))
+      Ok((next_index, Arc::new(builder.build().unwrap())))
     },
   }
 }
@@ -1210,8 +1241,8 @@ mod tests {
     assert!(f2.is_ok());
 
     let mut fields = vec![];
-    fields.push(Rc::new(f1.unwrap()));
-    fields.push(Rc::new(f2.unwrap()));
+    fields.push(Arc::new(f1.unwrap()));
+    fields.push(Arc::new(f2.unwrap()));
 
     let result = Type::group_type_builder("foo")
       .with_repetition(Repetition::REPEATED)
@@ -1251,10 +1282,10 @@ mod tests {
       .with_logical_type(LogicalType::LIST)
       .build()
       .unwrap();
-    let root_tp_rc = Rc::new(root_tp);
+    let root_tp_rc = Arc::new(root_tp);
 
     let descr = ColumnDescriptor::new(
-      Rc::new(tp),
+      Arc::new(tp),
       Some(root_tp_rc.clone()),
       4,
       1,
@@ -1293,16 +1324,16 @@ mod tests {
       .with_repetition(Repetition::REQUIRED)
       .with_logical_type(LogicalType::INT_32)
       .build()?;
-    fields.push(Rc::new(inta));
+    fields.push(Arc::new(inta));
     let intb = Type::primitive_type_builder("b", PhysicalType::INT64)
       .with_logical_type(LogicalType::INT_64)
       .build()?;
-    fields.push(Rc::new(intb));
+    fields.push(Arc::new(intb));
     let intc = Type::primitive_type_builder("c", PhysicalType::BYTE_ARRAY)
       .with_repetition(Repetition::REPEATED)
       .with_logical_type(LogicalType::UTF8)
       .build()?;
-    fields.push(Rc::new(intc));
+    fields.push(Arc::new(intc));
 
     // 3-level list encoding
     let item1 = Type::primitive_type_builder("item1", PhysicalType::INT64)
@@ -1317,19 +1348,19 @@ mod tests {
     let list = Type::group_type_builder("records")
       .with_repetition(Repetition::REPEATED)
       .with_logical_type(LogicalType::LIST)
-      .with_fields(&mut vec![Rc::new(item1), Rc::new(item2), Rc::new(item3)])
+      .with_fields(&mut vec![Arc::new(item1), Arc::new(item2), Arc::new(item3)])
       .build()?;
     let bag = Type::group_type_builder("bag")
       .with_repetition(Repetition::OPTIONAL)
-      .with_fields(&mut vec![Rc::new(list)])
+      .with_fields(&mut vec![Arc::new(list)])
       .build()?;
-    fields.push(Rc::new(bag));
+    fields.push(Arc::new(bag));
 
     let schema = Type::group_type_builder("schema")
       .with_repetition(Repetition::REPEATED)
       .with_fields(&mut fields)
       .build()?;
-    let descr = SchemaDescriptor::new(Rc::new(schema));
+    let descr = SchemaDescriptor::new(Arc::new(schema));
 
     let nleaves = 6;
     assert_eq!(descr.num_columns(), nleaves);
@@ -1384,7 +1415,7 @@ mod tests {
     }
     ";
     let schema = parse_message_type(message_type).expect("should parse schema");
-    let descr = SchemaDescriptor::new(Rc::new(schema));
+    let descr = SchemaDescriptor::new(Arc::new(schema));
     // required int32 a
     assert_eq!(descr.column(0).max_def_level(), 0);
     assert_eq!(descr.column(0).max_rep_level(), 0);
@@ -1478,7 +1509,7 @@ mod tests {
   fn test_new_group_type(name: &str, repetition: Repetition, types: Vec<Type>) -> Type {
     let mut fields = Vec::new();
     for tpe in types {
-      fields.push(Rc::new(tpe))
+      fields.push(Arc::new(tpe))
     }
     Type::group_type_builder(name)
       .with_repetition(repetition)
@@ -1731,7 +1762,44 @@ mod tests {
     let expected_schema = parse_message_type(message_type).unwrap();
     let thrift_schema = to_thrift(&expected_schema).unwrap();
     let result_schema = from_thrift(&thrift_schema).unwrap();
-    assert_eq!(result_schema, Rc::new(expected_schema));
+    assert_eq!(result_schema, Arc::new(expected_schema));
+  }
+
+  #[test]
+  fn test_schema_type_thrift_conversion_max_depth() {
+    // Schema with 3 nested groups, leaf field has depth 4
+    let message_type = "
+    message schema {
+      REQUIRED group a {
+        REQUIRED group b {
+          REQUIRED group c {
+            REQUIRED INT32 d;
+          }
+        }
+      }
+    }
+    ";
+    let schema = parse_message_type(message_type).unwrap();
+    let thrift_schema = to_thrift(&schema).unwrap();
+    assert_eq!(from_thrift_with_max_depth(&thrift_schema, 4).unwrap(), Arc::new(schema));
+    assert_eq!(
+      from_thrift_with_max_depth(&thrift_schema, 3).unwrap_err(),
+      general_err!("Schema nesting depth exceeds maximum of 3")
+    );
+
+    // Pathologically nested schema fails at the default limit
+    let mut elements = vec![thrift_schema[0].clone()];
+    for _ in 0..100_000 {
+      elements.push(thrift_schema[1].clone());
+    }
+    elements.push(thrift_schema[4].clone());
+    assert_eq!(
+      from_thrift(&elements).unwrap_err(),
+      general_err!(
+        "Schema nesting depth exceeds maximum of {}",
+        DEFAULT_MAX_NESTING_DEPTH
+      )
+    );
   }
 
   #[test]
@@ -1747,7 +1815,7 @@ mod tests {
     let expected_schema = parse_message_type(message_type).unwrap();
     let thrift_schema = to_thrift(&expected_schema).unwrap();
     let result_schema = from_thrift(&thrift_schema).unwrap();
-    assert_eq!(result_schema, Rc::new(expected_schema));
+    assert_eq!(result_schema, Arc::new(expected_schema));
   }
 
   // Tests schema conversion from thrift, when num_children is set to Some(0) for a
@@ -1776,7 +1844,7 @@ mod tests {
     }
 
     let result_schema = from_thrift(&thrift_schema).unwrap();
-    assert_eq!(result_schema, Rc::new(expected_schema));
+    assert_eq!(result_schema, Arc::new(expected_schema));
   }
 
   // Sometimes parquet-cpp sets repetition level for the root node, which is against
@@ -1796,6 +1864,6 @@ mod tests {
     thrift_schema[0].repetition_type = Some(Repetition::REQUIRED.into());
 
     let result_schema = from_thrift(&thrift_schema).unwrap();
-    assert_eq!(result_schema, Rc::new(expected_schema));
+    assert_eq!(result_schema, Arc::new(expected_schema));
   }
 }
@@ -17,9 +17,11 @@
 
 use std::{
   cmp,
-  mem::{size_of, transmute_copy},
+  mem::{self, size_of, transmute_copy},
+  thread,
 };
 
+use byteorder::{ByteOrder, LittleEndian};
 use errors::{ParquetError, Result};
 use util::{bit_packing::unpack32, memory::ByteBufferPtr};
 
@@ -86,6 +88,19 @@ pub fn ceil(value: i64, divisor: i64) -> i64 {
   result
 }
 
+/// Returns the ceil of value/divisor, or `None` if divisor is zero or the computation
+/// overflows. Should be used instead of `ceil` when value is derived from untrusted
+/// input, e.g. sizes read from page headers.
+#[inline]
+pub fn checked_ceil(value: i64, divisor: i64) -> Option<i64> {
+  let result = value.checked_div(divisor)?;
+  if value.checked_rem(divisor)? != 0 {
+    result.checked_add(1)
+  } else {
+    Some(result)
+  }
+}
+
 /// Returns ceil(log2(x))
 #[inline]
 pub fn log2(mut x: u64) -> i32 {
@@ -131,6 +146,116 @@ pub fn num_required_bits(x: u64) -> usize {
   0
 }
 
+/// Packs value `$i` of a group of 8 into 64-bit words at bit offset `$i * $width`.
+/// Second OR writes the bits spilling into the next word, or 0 if there are none, so
+/// no branch is needed.
+macro_rules! pack8_values {
+  ($words:ident, $values:ident, $width:expr, $($i:tt)*) => {$(
+    let v = $values[$i] & ((1u64 << $width) - 1);
+    $words[$i * $width / 64] |= v << ($i * $width % 64);
+    $words[($i * $width + $width - 1) / 64] |= (v >> 1) >> (63 - $i * $width % 64);
+  )*};
+}
+
+/// Reverse of `pack8_values`: extracts value `$i` of a group of 8 from 64-bit words.
+macro_rules! unpack8_values {
+  ($words:ident, $out:ident, $width:expr, $($i:tt)*) => {$(
+    $out[$i] = ($words[$i * $width / 64] >> ($i * $width % 64)
+      | ($words[($i * $width + $width - 1) / 64] << 1) << (63 - $i * $width % 64))
+      & ((1u64 << $width) - 1);
+  )*};
+}
+
+/// Generates `pack8` and `unpack8` along with an unrolled kernel for every bit width.
+/// 8 values take at most 256 bits, which are assembled in four 64-bit words; all word
+/// indices and shifts are constants, so kernels have no per-value branches.
+macro_rules! bit_pack8_kernels {
+  ($($width:tt: $pack:ident, $unpack:ident;)*) => {
+    $(
+      #[inline]
+      fn $pack(values: &[u64; 8], out: &mut [u8]) {
+        let mut words = [0u64; 4];
+        pack8_values!(words, values, $width, 0 1 2 3 4 5 6 7);
+        let mut bytes = [0u8; 32];
+        LittleEndian::write_u64_into(&words, &mut bytes);
+        out[..$width].copy_from_slice(&bytes[..$width]);
+      }
+
+      #[inline]
+      fn $unpack(bytes: &[u8], out: &mut [u64; 8]) {
+        let mut buf = [0u8; 32];
+        buf[..$width].copy_from_slice(&bytes[..$width]);
+        let mut words = [0u64; 4];
+        LittleEndian::read_u64_into(&buf, &mut words);
+        unpack8_values!(words, out, $width, 0 1 2 3 4 5 6 7);
+      }
+    )*
+
+    /// Bit-packs a group of 8 values into `out`, using `bit_width` bits per value.
+    /// Layout is the same as the bit-packed runs of the RLE/bit-packing hybrid
+    /// encoding, so exactly `bit_width` bytes of `out` are written. Bits of a value
+    /// above `bit_width` are ignored.
+    ///
+    /// Panics if `bit_width` is greater than 32 or `out` is shorter than `bit_width`.
+    #[inline]
+    pub fn pack8(values: &[u64; 8], bit_width: usize, out: &mut [u8]) {
+      match bit_width {
+        0 => {},
+        $($width => $pack(values, out),)*
+        _ => panic!("Bit width {} is greater than 32", bit_width),
+      }
+    }
+
+    /// Unpacks a group of 8 values, `bit_width` bits each, from `bytes` into `out`.
+    /// Reverse of [`pack8`](fn.pack8.html); reads exactly `bit_width` bytes.
+    ///
+    /// Panics if `bit_width` is greater than 32 or `bytes` is shorter than `bit_width`.
+    #[inline]
+    pub fn unpack8(bytes: &[u8], bit_width: usize, out: &mut [u64; 8]) {
+      match bit_width {
+        0 => *out = [0; 8],
+        $($width => $unpack(bytes, out),)*
+        _ => panic!("Bit width {} is greater than 32", bit_width),
+      }
+    }
+  };
+}
+
+bit_pack8_kernels! {
+  1: pack8_1, unpack8_1;
+  2: pack8_2, unpack8_2;
+  3: pack8_3, unpack8_3;
+  4: pack8_4, unpack8_4;
+  5: pack8_5, unpack8_5;
+  6: pack8_6, unpack8_6;
+  7: pack8_7, unpack8_7;
+  8: pack8_8, unpack8_8;
+  9: pack8_9, unpack8_9;
+  10: pack8_10, unpack8_10;
+  11: pack8_11, unpack8_11;
+  12: pack8_12, unpack8_12;
+  13: pack8_13, unpack8_13;
+  14: pack8_14, unpack8_14;
+  15: pack8_15, unpack8_15;
+  16: pack8_16, unpack8_16;
+  17: pack8_17, unpack8_17;
+  18: pack8_18, unpack8_18;
+  19: pack8_19, unpack8_19;
+  20: pack8_20, unpack8_20;
+  21: pack8_21, unpack8_21;
+  22: pack8_22, unpack8_22;
+  23: pack8_23, unpack8_23;
+  24: pack8_24, unpack8_24;
+  25: pack8_25, unpack8_25;
+  26: pack8_26, unpack8_26;
+  27: pack8_27, unpack8_27;
+  28: pack8_28, unpack8_28;
+  29: pack8_29, unpack8_29;
+  30: pack8_30, unpack8_30;
+  31: pack8_31, unpack8_31;
+  32: pack8_32, unpack8_32;
+}
+
 /// Utility class for writing bit/byte streams. This class can write data in either
 /// bit packed or byte aligned fashion.
 pub struct BitWriter {
@@ -140,6 +265,8 @@ pub struct BitWriter {
   byte_offset: usize,
   bit_offset: usize,
   start: usize,
+  // Whether to panic if writer is dropped with unflushed bits
+  drop_check: bool,
 }
 
 impl BitWriter {
@@ -151,6 +278,7 @@ impl BitWriter {
       byte_offset: 0,
       bit_offset: 0,
       start: 0,
+      drop_check: false,
     }
   }
 
@@ -166,15 +294,27 @@ impl BitWriter {
       byte_offset: start,
       bit_offset: 0,
       start,
+      drop_check: false,
     }
   }
 
+  /// Enables check that panics if the writer is dropped while it still has buffered
+  /// bits that were not flushed with `flush` or `consume`.
+  ///
+  /// This is meant to catch encoders that lose the last partial byte during development,
+  /// by default dropping the writer is silent.
+  pub fn with_drop_check(mut self) -> Self {
+    self.drop_check = true;
+    self
+  }
+
   /// Consumes and returns the current buffer.
   #[inline]
   pub fn consume(mut self) -> Vec<u8> {
     self.flush();
-    self.buffer.truncate(self.byte_offset);
-    self.buffer
+    let mut buffer = mem::replace(&mut self.buffer, vec![]);
+    buffer.truncate(self.byte_offset);
+    buffer
   }
 
   /// Flushes the internal buffered bits and returns the buffer's content.
@@ -308,6 +448,29 @@ impl BitWriter {
     true
   }
 
+  /// Writes `bytes` to the next aligned byte, prefixed with their length as 4-byte
+  /// little-endian integer. This is the layout of PLAIN encoded BYTE_ARRAY values.
+  ///
+  /// Returns false if there's not enough room left, or `bytes` is longer than
+  /// `u32::MAX` and its length cannot be encoded, nothing is written in these cases.
+  /// True otherwise.
+  #[inline]
+  pub fn put_byte_array(&mut self, bytes: &[u8]) -> bool {
+    let len = bytes.len() as u32;
+    if len as usize != bytes.len() {
+      return false;
+    }
+    match self.get_next_byte_ptr(size_of::<u32>() + bytes.len()) {
+      Ok(ptr) => {
+        let (len_ptr, data_ptr) = ptr.split_at_mut(size_of::<u32>());
+        LittleEndian::write_u32(len_ptr, len);
+        data_ptr.copy_from_slice(bytes);
+        true
+      },
+      Err(_) => false,
+    }
+  }
+
   /// Writes `val` of `num_bytes` bytes at the designated `offset`. The `offset` is the
   /// offset starting from the beginning of the internal buffer that this writer
   /// maintains. Note that this will overwrite any existing data between `offset` and
@@ -362,6 +525,17 @@ impl BitWriter {
   }
 }
 
+impl Drop for BitWriter {
+  fn drop(&mut self) {
+    if self.drop_check && self.bit_offset != 0 && !thread::panicking() {
+      panic!(
+        "BitWriter dropped with {} unflushed bits, call `flush` or `consume`",
+        self.bit_offset
+      );
+    }
+  }
+}
+
 /// Maximum byte length for a VLQ encoded integer
 /// MAX_VLQ_BYTE_LEN = 5 for i32, and MAX_VLQ_BYTE_LEN = 10 for i64
 pub const MAX_VLQ_BYTE_LEN: usize = 10;
@@ -514,7 +688,18 @@ impl BitReader {
       }
     }
 
-    assert!(values_to_read - i < 32);
+    // Unpack remaining groups of 8 values
+    let mut group = [0u64; 8];
+    while values_to_read - i >= 8 {
+      unpack8(&self.buffer.data()[self.byte_offset..], num_bits, &mut group);
+      self.byte_offset += num_bits;
+      for v in group.iter() {
+        batch[i] = unsafe { transmute_copy::<u64, T>(v) };
+        i += 1;
+      }
+    }
+
+    assert!(values_to_read - i < 8);
 
     self.reload_buffer_values();
     while i < values_to_read {
@@ -536,8 +721,10 @@ impl BitReader {
   /// Otherwise `None`.
   #[inline]
   pub fn get_aligned<T: Default>(&mut self, num_bytes: usize) -> Option<T> {
-    let bytes_read = ceil(self.bit_offset as i64, 8) as usize;
-    if self.byte_offset + bytes_read + num_bytes > self.total_bytes {
+    // Number of bytes can be read from data, check for overflow
+    let bytes_read = checked_ceil(self.bit_offset as i64, 8)? as usize;
+    let end = self.byte_offset.checked_add(bytes_read)?.checked_add(num_bytes)?;
+    if end > self.total_bytes {
       return None;
     }
 
@@ -621,6 +808,8 @@ mod tests {
     *,
   };
 
+  use data_type::{ByteArray, ByteArrayType};
+  use encodings::decoding::{Decoder, PlainDecoder};
   use rand::distributions::{Distribution, Standard};
 
   #[test]
@@ -638,6 +827,18 @@ mod tests {
     assert_eq!(ceil(10000000000, 1000000000), 10);
   }
 
+  #[test]
+  fn test_checked_ceil() {
+    assert_eq!(checked_ceil(0, 1), Some(0));
+    assert_eq!(checked_ceil(9, 8), Some(2));
+    assert_eq!(checked_ceil(10000000000, 10), Some(1000000000));
+    assert_eq!(checked_ceil(i64::max_value(), 1), Some(i64::max_value()));
+    assert_eq!(checked_ceil(i64::max_value(), 8), Some(i64::max_value() / 8 + 1));
+    assert_eq!(checked_ceil(i64::max_value() - 7, 8), Some(i64::max_value() / 8));
+    assert_eq!(checked_ceil(i64::min_value(), -1), None);
+    assert_eq!(checked_ceil(1, 0), None);
+  }
+
   #[test]
   fn test_bit_reader_get_byte_offset() {
     let buffer = vec![255; 10];
@@ -683,6 +884,9 @@ mod tests {
     assert_eq!(bit_reader.get_value::<i32>(1), None);
     bit_reader.reset(buffer.all());
     assert_eq!(bit_reader.get_aligned::<i32>(3), None);
+    // Number of bytes that overflows offset
+    assert_eq!(bit_reader.get_value::<i32>(3), Some(5));
+    assert_eq!(bit_reader.get_aligned::<i32>(usize::max_value()), None);
   }
 
   #[test]
@@ -790,6 +994,28 @@ mod tests {
     assert_eq!(res1, &res2[..]);
   }
 
+  #[test]
+  #[should_panic(expected = "BitWriter dropped with 3 unflushed bits")]
+  fn test_bit_writer_drop_check_unflushed() {
+    let mut writer = BitWriter::new(8).with_drop_check();
+    writer.put_value(5, 3);
+  }
+
+  #[test]
+  fn test_bit_writer_drop_check_flushed() {
+    let mut writer = BitWriter::new(8).with_drop_check();
+    writer.put_value(5, 3);
+    writer.flush();
+
+    let mut writer = BitWriter::new(8).with_drop_check();
+    writer.put_value(5, 3);
+    assert_eq!(writer.consume(), vec![5]);
+
+    // Check is disabled by default
+    let mut writer = BitWriter::new(8);
+    writer.put_value(5, 3);
+  }
+
   #[test]
   fn test_put_get_bool() {
     let len = 8;
@@ -884,6 +1110,31 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_pack8_unpack8_roundtrip() {
+    for bit_width in 1..33 {
+      let mask = (1u64 << bit_width) - 1;
+      let mut values = [0u64; 8];
+      for (i, v) in random_numbers::<u64>(8).iter().enumerate() {
+        values[i] = v & mask;
+      }
+
+      let mut packed = vec![0u8; bit_width];
+      pack8(&values, bit_width, &mut packed);
+
+      // Layout must match values written one by one with `BitWriter`
+      let mut writer = BitWriter::new(bit_width);
+      for v in values.iter() {
+        assert!(writer.put_value(*v, bit_width));
+      }
+      assert_eq!(packed, writer.consume());
+
+      let mut unpacked = [0u64; 8];
+      unpack8(&packed, bit_width, &mut unpacked);
+      assert_eq!(unpacked, values);
+    }
+  }
+
   #[test]
   fn test_get_batch() {
     const SIZE: &[usize] = &[1, 31, 32, 33, 128, 129];
@@ -1004,6 +1255,30 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_put_byte_array() {
+    let values: Vec<&[u8]> = vec![b"parquet", b"", b"a", &[0, 255, 1]];
+    let mut writer = BitWriter::new(32);
+    writer.put_value(1, 3);
+    for value in &values {
+      assert!(writer.put_byte_array(value));
+    }
+    assert_eq!(writer.bytes_written(), 1 + values.len() * 4 + 11);
+
+    // Not enough room left, writer is not changed
+    assert!(!writer.put_byte_array(&[1; 8]));
+    assert_eq!(writer.bytes_written(), 1 + values.len() * 4 + 11);
+
+    let buffer = ByteBufferPtr::new(writer.consume()).start_from(1);
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(0);
+    decoder.set_data(buffer, values.len()).unwrap();
+    let mut result = vec![ByteArray::new(); values.len()];
+    assert_eq!(decoder.get(&mut result).unwrap(), values.len());
+    for (actual, expected) in result.iter().zip(values.iter()) {
+      assert_eq!(actual.data(), *expected);
+    }
+  }
+
   #[test]
   fn test_put_vlq_int() {
     let total = 64;
@@ -15,8 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use file::reader::ParquetReader;
-use std::{cmp, fs::File, io::*, sync::Mutex};
+use errors;
+use file::{reader::ParquetReader, writer::ParquetWriter};
+use std::{cell::RefCell, cmp, io::*, rc::Rc, sync::Mutex};
 
 // ----------------------------------------------------------------------
 // Read/Write wrappers for `File`.
@@ -78,19 +79,103 @@ impl<R: ParquetReader> Position for FileSource<R> {
   fn pos(&self) -> u64 { self.start }
 }
 
-/// Struct that represents `File` output stream with position tracking.
+/// Number of bytes read from a file at a time by [`CachedFileSource`].
+const CACHED_READ_SIZE: usize = 8 * 1024;
+
+/// Struct that represents a slice of a file data, similar to `FileSource`, but which
+/// keeps the bytes it has read in memory.
+///
+/// Data is read from the file lazily in blocks of `CACHED_READ_SIZE` bytes, never past
+/// the end of the slice. Clones share read bytes, so reading the same slice again
+/// through a clone does not perform any IO for bytes that were already read.
+pub struct CachedFileSource<R: ParquetReader> {
+  chunk: Rc<RefCell<CachedChunk<R>>>,
+  pos: usize, // position in the slice
+}
+
+struct CachedChunk<R: ParquetReader> {
+  file: R,
+  start: u64,    // start position in a file
+  length: usize, // length of the slice
+  data: Vec<u8>, // bytes read so far, from the start of the slice
+}
+
+impl<R: ParquetReader> CachedFileSource<R> {
+  /// Creates new cached file reader with start and length from a file handle.
+  /// Returns error if the file handle cannot be cloned.
+  pub fn new(fd: &R, start: u64, length: usize) -> errors::Result<Self> {
+    let chunk = CachedChunk {
+      file: fd.try_clone()?,
+      start,
+      length,
+      data: Vec::new(),
+    };
+    Ok(Self {
+      chunk: Rc::new(RefCell::new(chunk)),
+      pos: 0,
+    })
+  }
+}
+
+impl<R: ParquetReader> CachedChunk<R> {
+  /// Reads next block of the slice from the file and appends it to the cached data.
+  /// Returns number of bytes read, 0 if the slice or the file is exhausted.
+  fn read_next(&mut self) -> Result<usize> {
+    let offset = self.data.len();
+    let bytes_to_read = cmp::min(self.length - offset, CACHED_READ_SIZE);
+    if bytes_to_read == 0 {
+      return Ok(0);
+    }
+    self
+      .file
+      .seek(SeekFrom::Start(self.start + offset as u64))?;
+    self.data.resize(offset + bytes_to_read, 0);
+    let res = self.file.read(&mut self.data[offset..]);
+    self.data.truncate(offset + *res.as_ref().unwrap_or(&0));
+    res
+  }
+}
+
+impl<R: ParquetReader> Clone for CachedFileSource<R> {
+  fn clone(&self) -> Self {
+    Self {
+      chunk: self.chunk.clone(),
+      pos: self.pos,
+    }
+  }
+}
+
+impl<R: ParquetReader> Read for CachedFileSource<R> {
+  fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+    let mut chunk = self.chunk.borrow_mut();
+    if self.pos == chunk.data.len() {
+      chunk.read_next()?;
+    }
+
+    let bytes_to_read = cmp::min(buf.len(), chunk.data.len() - self.pos);
+    buf[..bytes_to_read].copy_from_slice(&chunk.data[self.pos..self.pos + bytes_to_read]);
+    self.pos += bytes_to_read;
+    Ok(bytes_to_read)
+  }
+}
+
+impl<R: ParquetReader> Position for CachedFileSource<R> {
+  fn pos(&self) -> u64 { self.chunk.borrow().start + self.pos as u64 }
+}
+
+/// Struct that represents output stream with position tracking.
 /// Used as a sink in file writer.
-pub struct FileSink {
-  buf: BufWriter<File>,
+pub struct FileSink<W: ParquetWriter> {
+  buf: BufWriter<W>,
   // This is not necessarily position in the underlying file,
   // but rather current position in the sink.
   pos: u64,
 }
 
-impl FileSink {
+impl<W: ParquetWriter> FileSink<W> {
   /// Creates new file sink.
   /// Position is set to whatever position file has.
-  pub fn new(file: &File) -> Self {
+  pub fn new(file: &W) -> Self {
     let mut owned_file = file.try_clone().unwrap();
     let pos = owned_file.seek(SeekFrom::Current(0)).unwrap();
     Self {
@@ -100,7 +185,7 @@ impl FileSink {
   }
 }
 
-impl Write for FileSink {
+impl<W: ParquetWriter> Write for FileSink<W> {
   fn write(&mut self, buf: &[u8]) -> Result<usize> {
     let num_bytes = self.buf.write(buf)?;
     self.pos += num_bytes as u64;
@@ -110,7 +195,7 @@ impl Write for FileSink {
   fn flush(&mut self) -> Result<()> { self.buf.flush() }
 }
 
-impl Position for FileSink {
+impl<W: ParquetWriter> Position for FileSink<W> {
   fn pos(&self) -> u64 { self.pos }
 }
 
@@ -186,6 +271,30 @@ mod tests {
     assert_eq!(buf, vec![b'P', b'A', b'R', b'1']);
   }
 
+  #[test]
+  fn test_io_cached_read() {
+    let mut buf = vec![0; 8];
+    let file = get_test_file("alltypes_plain.parquet");
+    let mut src = CachedFileSource::new(&file, 0, 4).unwrap();
+
+    let bytes_read = src.read(&mut buf[0..2]).unwrap();
+    assert_eq!(bytes_read, 2);
+    assert_eq!(src.pos(), 2);
+
+    // Clone shares cached bytes, but keeps its own position
+    let mut src_clone = src.clone();
+    let bytes_read = src.read(&mut buf[2..]).unwrap();
+    assert_eq!(bytes_read, 2);
+    assert_eq!(buf, vec![b'P', b'A', b'R', b'1', 0, 0, 0, 0]);
+    assert_eq!(src.read(&mut buf[..]).unwrap(), 0);
+    assert_eq!(src.pos(), 4);
+
+    let bytes_read = src_clone.read(&mut buf[..]).unwrap();
+    assert_eq!(bytes_read, 2);
+    assert_eq!(&buf[0..2], &[b'R', b'1']);
+    assert_eq!(src_clone.pos(), 4);
+  }
+
   #[test]
   fn test_io_write_with_pos() {
     let mut file = get_temp_file("file_sink_test", &[b'a', b'b', b'c']);
@@ -18,19 +18,19 @@
 //! Utility methods and structs for working with memory.
 
 use std::{
-  cell::Cell,
+  cmp,
   fmt::{Debug, Display, Formatter, Result as FmtResult},
   io::{Result as IoResult, Write},
   mem,
   ops::{Index, IndexMut},
-  rc::{Rc, Weak},
+  sync::{Arc, Mutex, Weak},
 };
 
 // ----------------------------------------------------------------------
 // Memory Tracker classes
 
 /// Reference counted pointer for [`MemTracker`].
-pub type MemTrackerPtr = Rc<MemTracker>;
+pub type MemTrackerPtr = Arc<MemTracker>;
 /// Non-owning reference for [`MemTracker`].
 pub type WeakMemTrackerPtr = Weak<MemTracker>;
 
@@ -39,7 +39,7 @@ pub type WeakMemTrackerPtr = Weak<MemTracker>;
 pub struct MemTracker {
   // In the tuple, the first element is the current memory allocated (in bytes),
   // and the second element is the maximum memory allocated so far (in bytes).
-  memory_usage: Cell<(i64, i64)>,
+  memory_usage: Mutex<(i64, i64)>,
 }
 
 impl MemTracker {
@@ -47,25 +47,23 @@ impl MemTracker {
   #[inline]
   pub fn new() -> MemTracker {
     MemTracker {
-      memory_usage: Cell::new((0, 0)),
+      memory_usage: Mutex::new((0, 0)),
     }
   }
 
   /// Returns the current memory consumption, in bytes.
-  pub fn memory_usage(&self) -> i64 { self.memory_usage.get().0 }
+  pub fn memory_usage(&self) -> i64 { self.memory_usage.lock().unwrap().0 }
 
   /// Returns the maximum memory consumption so far, in bytes.
-  pub fn max_memory_usage(&self) -> i64 { self.memory_usage.get().1 }
+  pub fn max_memory_usage(&self) -> i64 { self.memory_usage.lock().unwrap().1 }
 
   /// Adds `num_bytes` to the memory consumption tracked by this memory tracker.
   #[inline]
   pub fn alloc(&self, num_bytes: i64) {
-    let (current, mut maximum) = self.memory_usage.get();
+    let mut memory_usage = self.memory_usage.lock().unwrap();
+    let (current, maximum) = *memory_usage;
     let new_current = current + num_bytes;
-    if new_current > maximum {
-      maximum = new_current
-    }
-    self.memory_usage.set((new_current, maximum));
+    *memory_usage = (new_current, cmp::max(new_current, maximum));
   }
 }
 
@@ -192,6 +190,13 @@ impl<T: Clone> Buffer<T> {
   pub fn mem_tracker(&self) -> &MemTrackerPtr { self.mem_tracker.as_ref().unwrap() }
 }
 
+impl ByteBuffer {
+  /// Converts this buffer into [`ByteBufferPtr`], moving the underlying data without
+  /// copying. Memory tracker, if any, is transferred to the returned buffer.
+  #[inline]
+  pub fn into_byte_buffer_ptr(mut self) -> ByteBufferPtr { self.consume() }
+}
+
 impl<T: Sized + Clone> Index<usize> for Buffer<T> {
   type Output = T;
 
@@ -241,9 +246,12 @@ impl<T: Clone> Drop for Buffer<T> {
 /// An representation of a slice on a reference-counting and read-only byte array.
 /// Sub-slices can be further created from this. The byte array will be released
 /// when all slices are dropped.
-#[derive(Clone, Debug)]
+///
+/// Cloning is cheap: it only increments reference count of the byte array and never
+/// copies the data.
+#[derive(Debug)]
 pub struct BufferPtr<T> {
-  data: Rc<Vec<T>>,
+  data: Arc<Vec<T>>,
   start: usize,
   len: usize,
   // TODO: will this create too many references? rethink about this.
@@ -255,7 +263,7 @@ impl<T> BufferPtr<T> {
   pub fn new(v: Vec<T>) -> Self {
     let len = v.len();
     Self {
-      data: Rc::new(v),
+      data: Arc::new(v),
       start: 0,
       len,
       mem_tracker: None,
@@ -291,6 +299,10 @@ impl<T> BufferPtr<T> {
   /// Returns `true` if this buffer has memory tracker, `false` otherwise.
   pub fn is_mem_tracked(&self) -> bool { self.mem_tracker.is_some() }
 
+  /// Returns number of buffers that share the underlying data, including this one.
+  /// Useful for checking that the data is released once buffers are dropped.
+  pub fn strong_count(&self) -> usize { Arc::strong_count(&self.data) }
+
   /// Returns a shallow copy of the buffer.
   /// Reference counted pointer to the data is copied.
   pub fn all(&self) -> BufferPtr<T> {
@@ -325,6 +337,10 @@ impl<T> BufferPtr<T> {
   }
 }
 
+impl<T> Clone for BufferPtr<T> {
+  fn clone(&self) -> Self { self.all() }
+}
+
 impl<T: Sized> Index<usize> for BufferPtr<T> {
   type Output = T;
 
@@ -341,8 +357,8 @@ impl<T: Debug> Display for BufferPtr<T> {
 impl<T> Drop for BufferPtr<T> {
   fn drop(&mut self) {
     if self.is_mem_tracked()
-      && Rc::strong_count(&self.data) == 1
-      && Rc::weak_count(&self.data) == 0
+      && Arc::strong_count(&self.data) == 1
+      && Arc::weak_count(&self.data) == 0
     {
       let mc = self.mem_tracker.as_ref().unwrap();
       mc.alloc(-(self.data.capacity() as i64));
@@ -360,7 +376,7 @@ mod tests {
 
   #[test]
   fn test_byte_buffer_mem_tracker() {
-    let mem_tracker = Rc::new(MemTracker::new());
+    let mem_tracker = Arc::new(MemTracker::new());
 
     let mut buffer = ByteBuffer::new().with_mem_tracker(mem_tracker.clone());
     buffer.set_data(vec![0; 10]);
@@ -396,7 +412,7 @@ mod tests {
 
   #[test]
   fn test_byte_ptr_mem_tracker() {
-    let mem_tracker = Rc::new(MemTracker::new());
+    let mem_tracker = Arc::new(MemTracker::new());
 
     let mut buffer = ByteBuffer::new().with_mem_tracker(mem_tracker.clone());
     buffer.set_data(vec![0; 60]);
@@ -459,6 +475,26 @@ mod tests {
     assert_eq!(buffer.data(), values.as_slice());
   }
 
+  #[test]
+  fn test_byte_buffer_into_byte_buffer_ptr() {
+    let mem_tracker = Arc::new(MemTracker::new());
+    let mut buffer = ByteBuffer::new().with_mem_tracker(mem_tracker.clone());
+    buffer.set_data((0..20).collect());
+    let capacity = buffer.capacity() as i64;
+    let data_ptr = buffer.data().as_ptr();
+
+    let ptr = buffer.into_byte_buffer_ptr();
+    let expected: Vec<u8> = (0..20).collect();
+    assert_eq!(ptr.data(), expected.as_slice());
+    // Data is moved, not copied
+    assert_eq!(ptr.data().as_ptr(), data_ptr);
+    assert!(ptr.is_mem_tracked());
+    assert_eq!(mem_tracker.memory_usage(), capacity);
+
+    drop(ptr);
+    assert_eq!(mem_tracker.memory_usage(), 0);
+  }
+
   #[test]
   fn test_byte_ptr() {
     let values = (0..50).collect();
@@ -485,4 +521,26 @@ mod tests {
     let expected: Vec<u8> = (30..40).collect();
     assert_eq!(ptr4.as_ref(), expected.as_slice());
   }
+
+  #[test]
+  fn test_byte_ptr_clone() {
+    let ptr = ByteBufferPtr::new((0..50).collect()).start_from(10);
+    assert_eq!(ptr.strong_count(), 1);
+
+    {
+      let ptr2 = ptr.clone();
+      assert_eq!(ptr.strong_count(), 2);
+      assert_eq!(ptr2.strong_count(), 2);
+      assert_eq!(ptr2.start(), ptr.start());
+      assert_eq!(ptr2.data(), ptr.data());
+      // Both buffers point to the same memory
+      assert_eq!(ptr2.data().as_ptr(), ptr.data().as_ptr());
+
+      let ptr3 = ptr2.range(5, 5);
+      assert_eq!(ptr.strong_count(), 3);
+      assert_eq!(ptr3.data(), &ptr.data()[5..10]);
+    }
+
+    assert_eq!(ptr.strong_count(), 1);
+  }
 }